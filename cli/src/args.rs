@@ -1,4 +1,5 @@
 use clap::Parser;
+use gql_safeguard_lib::config::OutputFormat;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -6,27 +7,43 @@ use std::path::PathBuf;
 #[command(about = "Analyze GraphQL operations for missing @catch directives")]
 #[command(version)]
 pub struct Args {
-    // Root directory for GraphQL extraction
-    #[arg(default_value = ".")]
-    pub path: PathBuf,
+    // Root directory for GraphQL extraction (defaults to the working directory)
+    // `include`/`exclude` globs of the config file stay anchored to its directory
+    pub path: Option<PathBuf>,
 
-    // Which files contain GraphQL template literals
-    #[arg(long, default_value = "**/*.{ts,tsx}")]
-    pub pattern: String,
+    // Which files contain GraphQL template literals (replaces `include` of the config file)
+    #[arg(long)]
+    pub pattern: Option<String>,
 
-    // Skip build artifacts and dependencies
+    // Skip build artifacts and dependencies (replaces `exclude` of the config file)
     #[arg(long)]
     pub ignore: Option<String>,
 
+    // Template tags and functions holding GraphQL (e.g. `--tag gql,graphql,Relay.QL`)
+    #[arg(long = "tag", value_delimiter = ',')]
+    pub tags: Vec<String>,
+
+    // Only accept tags imported from GraphQL modules (react-relay, relay-runtime, @apollo/client, graphql-tag)
+    #[arg(long)]
+    pub import_aware: bool,
+
+    // Modules GraphQL tags may be imported from, implies --import-aware
+    #[arg(long = "tag-module", value_delimiter = ',')]
+    pub tag_modules: Vec<String>,
+
     // Enable project-relative path execution
     #[arg(long)]
     pub cwd: Option<PathBuf>,
 
+    // Configuration file instead of looking up gql-safeguard.toml or package.json
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
     // Primary operation mode
     #[command(subcommand)]
     pub command: Command,
 
-    // Enable debug output for troubleshooting
+    // Enable debug output for troubleshooting, written to stderr to keep reports parseable
     #[arg(long, short)]
     pub verbose: bool,
 }
@@ -38,9 +55,24 @@ pub enum Command {
         // Display fragment resolution for debugging
         #[arg(long)]
         show_trees: bool,
-        // Output results in JSON format for programmatic use
+        // Output results in JSON format for programmatic use, same as `--format json`
         #[arg(long)]
         json: bool,
+        // Output format: text, compact, json, sarif, github, codeclimate, junit or checkstyle
+        #[arg(long)]
+        format: Option<OutputFormat>,
+        // Only fail on violations that aren't accepted by this baseline file
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        // Accept all current violations by writing them to a baseline file
+        #[arg(long)]
+        write_baseline: Option<PathBuf>,
+        // Fail on templates that can't be analyzed statically (strict CI)
+        #[arg(long)]
+        fail_on_dynamic: bool,
+        // Fail on TypeScript or GraphQL syntax errors instead of skipping the document
+        #[arg(long)]
+        fail_on_parse_error: bool,
     },
     // Export extracted GraphQL for external tools
     Json,
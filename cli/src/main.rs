@@ -2,10 +2,30 @@ mod args;
 
 use args::{Args, Command};
 use clap::Parser;
-use gql_safeguard_lib::registry::process_glob;
-use gql_safeguard_lib::validate_registry::{validate_registry, JsonValidationResult};
+use gql_safeguard_lib::baseline::{Baseline, BaselineEntry, BaselineReport};
+use gql_safeguard_lib::config::{Config, OutputFormat};
+use gql_safeguard_lib::diagnostics::{promote_to_errors, DiagnosticKind};
+use gql_safeguard_lib::parsers::typescript_parser::{ExtractionOptions, DEFAULT_TAG_MODULES};
+use gql_safeguard_lib::registry::process_glob_with_options;
+use gql_safeguard_lib::reporters::checkstyle::to_checkstyle;
+use gql_safeguard_lib::reporters::codeclimate::to_codeclimate;
+use gql_safeguard_lib::reporters::compact::to_compact;
+use gql_safeguard_lib::reporters::github::to_github_annotations;
+use gql_safeguard_lib::reporters::junit::to_junit;
+use gql_safeguard_lib::reporters::sarif::to_sarif;
+use gql_safeguard_lib::validate_registry::{validate_registry_with_rules, JsonValidationResult};
+use std::path::PathBuf;
 use std::time::Instant;
 
+const DEFAULT_PATTERN: &str = "**/*.{ts,tsx,mts,cts,js,jsx,mjs,cjs,vue,svelte,astro,graphql,gql}";
+const DEFAULT_IGNORE_PATTERNS: [&str; 5] = [
+    "**/node_modules",
+    "**/.git",
+    "**/.yarn",
+    "**/.swc",
+    "**/*.xcassets",
+];
+
 fn main() -> anyhow::Result<()> {
     let start_time = Instant::now();
     let args = Args::parse();
@@ -14,67 +34,280 @@ fn main() -> anyhow::Result<()> {
     if let Some(cwd) = &args.cwd {
         std::env::set_current_dir(cwd)?;
         if args.verbose {
-            println!("Changed working directory to: {}", cwd.display());
+            eprintln!("Changed working directory to: {}", cwd.display());
+        }
+    }
+
+    // Settings of gql-safeguard.toml or package.json, flags take precedence
+    let loaded_config = match &args.config {
+        Some(path) => Some(Config::load(path)?),
+        None => Config::discover(&std::env::current_dir()?)?,
+    };
+    if args.verbose {
+        if let Some(loaded) = &loaded_config {
+            eprintln!("Config: {}", loaded.path.display());
         }
     }
+    let config = loaded_config
+        .as_ref()
+        .map(|loaded| loaded.config.clone())
+        .unwrap_or_default();
+    // Override, include and exclude globs are relative to the config file
+    let config_root = loaded_config.as_ref().map_or_else(
+        || PathBuf::from("."),
+        |loaded| loaded.directory().to_path_buf(),
+    );
+    let root_path = args.path.clone().unwrap_or_else(|| PathBuf::from("."));
+    let (config_include, config_exclude) = match &loaded_config {
+        Some(loaded) => (loaded.include()?, loaded.exclude()?),
+        None => (None, None),
+    };
+
+    let include_patterns: Vec<String> = match (&args.pattern, config_include) {
+        (Some(pattern), _) => vec![pattern.clone()],
+        (None, Some(include)) => include,
+        (None, None) => vec![DEFAULT_PATTERN.to_string()],
+    };
 
     // Skip common build artifacts and dependencies
-    let ignore_patterns = match args.ignore.as_deref() {
-        Some(pattern) => vec![pattern],
-        None => vec![
-            "**/node_modules",
-            "**/.git",
-            "**/.yarn",
-            "**/.swc",
-            "**/*.xcassets",
-        ],
+    let ignore_patterns: Vec<String> = match (&args.ignore, config_exclude) {
+        (Some(pattern), _) => vec![pattern.clone()],
+        (None, Some(exclude)) => exclude,
+        (None, None) => DEFAULT_IGNORE_PATTERNS
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect(),
+    };
+
+    let tags = if !args.tags.is_empty() {
+        args.tags.clone()
+    } else {
+        config
+            .tags
+            .clone()
+            .unwrap_or_else(|| vec!["gql".to_string(), "graphql".to_string()])
     };
 
     if args.verbose {
-        println!("Scanning path: {}", args.path.display());
-        println!("Pattern: {}", args.pattern);
-        println!("Ignore pattern: {}", ignore_patterns.join(", "));
+        eprintln!("Scanning path: {}", root_path.display());
+        eprintln!("Pattern: {}", include_patterns.join(", "));
+        eprintln!("Ignore pattern: {}", ignore_patterns.join(", "));
+        eprintln!("Tags: {}", tags.join(", "));
     }
 
     // Memory-efficient processing for large codebases
-    let patterns = vec![args.pattern.as_str()];
-    let registry = process_glob(&args.path, &patterns, &ignore_patterns)?;
+    let patterns: Vec<&str> = include_patterns.iter().map(String::as_str).collect();
+    let ignore_patterns: Vec<&str> = ignore_patterns.iter().map(String::as_str).collect();
+    let tag_modules = if !args.tag_modules.is_empty() {
+        Some(args.tag_modules.clone())
+    } else if let Some(tag_modules) = &config.tag_modules {
+        Some(tag_modules.clone())
+    } else if args.import_aware || config.import_aware == Some(true) {
+        Some(
+            DEFAULT_TAG_MODULES
+                .iter()
+                .map(|module| module.to_string())
+                .collect(),
+        )
+    } else {
+        None
+    };
+    if args.verbose {
+        if let Some(modules) = &tag_modules {
+            eprintln!("Tag modules: {}", modules.join(", "));
+        }
+    }
+    let extraction_options = ExtractionOptions {
+        tag_names: tags,
+        tag_modules,
+        ignore_policy: config.ignore_comments.unwrap_or_default(),
+    };
+    let registry =
+        process_glob_with_options(&root_path, &patterns, &ignore_patterns, &extraction_options)?;
 
     match args.command {
-        Command::Validate { show_trees, json } => {
+        Command::Validate {
+            show_trees,
+            json,
+            format,
+            baseline,
+            write_baseline,
+            fail_on_dynamic,
+            fail_on_parse_error,
+        } => {
+            let format = if json {
+                OutputFormat::Json
+            } else {
+                format.or(config.format).unwrap_or_default()
+            };
+            let fail_on_dynamic = fail_on_dynamic || config.fail_on_dynamic == Some(true);
+            let fail_on_parse_error =
+                fail_on_parse_error || config.fail_on_parse_error == Some(true);
+
             if args.verbose {
                 let elapsed = start_time.elapsed();
-                println!("Found {} files in {elapsed:.2?}", registry.file_count);
+                eprintln!("Found {} files in {elapsed:.2?}", registry.file_count);
             }
 
             if args.verbose {
                 let elapsed = start_time.elapsed();
-                println!("Found {} queries in {elapsed:.2?}", registry.queries.len());
+                eprintln!("Found {} queries in {elapsed:.2?}", registry.query_count());
             }
 
             // Use optimized registry-based validation for better performance
-            let validation_result = validate_registry(&registry);
+            let mut validation_result =
+                validate_registry_with_rules(&registry, &config.rule_set(&config_root)?);
+
+            // Accept the current violations instead of reporting them
+            if let Some(path) = &write_baseline {
+                let baseline = Baseline::from_result(&validation_result);
+                baseline.write(path)?;
+                println!(
+                    "📝 Wrote {} violation{} to the baseline {}",
+                    validation_result.errors.len(),
+                    if validation_result.errors.len() == 1 {
+                        ""
+                    } else {
+                        "s"
+                    },
+                    path.display()
+                );
+                return Ok(());
+            }
+            let baseline_report = match &baseline {
+                Some(path) => Baseline::load(path)?.apply(&mut validation_result),
+                None => BaselineReport::default(),
+            };
+
+            let mut diagnostics = registry
+                .diagnostics
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            if fail_on_dynamic {
+                promote_to_errors(&mut diagnostics, DiagnosticKind::DynamicTemplate);
+            }
+            if fail_on_parse_error {
+                promote_to_errors(&mut diagnostics, DiagnosticKind::ParseError);
+            }
+            let diagnostic_errors = diagnostics.iter().filter(|d| d.is_error()).count();
+
+            // Reports for editors and CI systems include the diagnostics next to the findings
+            // Their paths are relative to the working directory, the checkout root in CI
+            let report_root = std::env::current_dir()?;
+            let report = match format {
+                OutputFormat::Compact => {
+                    Some(to_compact(&validation_result, &diagnostics, &report_root))
+                }
+                OutputFormat::Sarif => Some(
+                    serde_json::to_string_pretty(&to_sarif(
+                        &validation_result,
+                        &diagnostics,
+                        &report_root,
+                    ))? + "\n",
+                ),
+                OutputFormat::Github => Some(to_github_annotations(
+                    &validation_result,
+                    &diagnostics,
+                    &report_root,
+                )),
+                OutputFormat::Codeclimate => Some(
+                    serde_json::to_string_pretty(&to_codeclimate(
+                        &validation_result,
+                        &diagnostics,
+                        &report_root,
+                    ))? + "\n",
+                ),
+                OutputFormat::Junit => Some(to_junit(
+                    &validation_result,
+                    &registry,
+                    &diagnostics,
+                    &report_root,
+                )),
+                OutputFormat::Checkstyle => Some(to_checkstyle(
+                    &validation_result,
+                    &diagnostics,
+                    &report_root,
+                )),
+                OutputFormat::Text | OutputFormat::Json => None,
+            };
 
-            if json {
+            if let Some(report) = report {
+                print!("{report}");
+                // stdout is the report file, the notice still has to show up in the CI log
+                if !baseline_report.fixed.is_empty() {
+                    eprint!("{}", fixed_baseline_notice(&baseline_report.fixed));
+                }
+
+                if validation_result.errors.is_empty() && diagnostic_errors == 0 {
+                    return Ok(());
+                } else {
+                    std::process::exit(1);
+                }
+            } else if format == OutputFormat::Json {
                 // Output JSON format for programmatic use
-                let json_result: JsonValidationResult = validation_result.into();
+                let mut json_result: JsonValidationResult = validation_result.into();
+                json_result.diagnostics = diagnostics;
+                json_result.fixed_baseline_entries = baseline_report.fixed;
                 let json_output = serde_json::to_string_pretty(&json_result)?;
                 println!("{json_output}");
 
-                if json_result.errors.is_empty() {
+                if json_result.errors.is_empty() && diagnostic_errors == 0 {
                     return Ok(());
                 } else {
                     std::process::exit(1);
                 }
             } else {
+                // Skipped GraphQL doesn't fail validation but shouldn't go unnoticed
+                for diagnostic in diagnostics.iter() {
+                    println!("{diagnostic}");
+                }
+                if !diagnostics.is_empty() {
+                    println!();
+                }
+
+                // Unused fragments don't fail validation but their directives are never checked
+                for warning in &validation_result.warnings {
+                    println!("{warning}");
+                }
+                if !validation_result.warnings.is_empty() {
+                    println!();
+                }
+
+                // Fixed entries should be dropped so they can't hide new violations later
+                if baseline_report.baselined > 0 {
+                    println!(
+                        "ℹ️  {} known violation{} accepted by the baseline",
+                        baseline_report.baselined,
+                        if baseline_report.baselined == 1 {
+                            " is"
+                        } else {
+                            "s are"
+                        }
+                    );
+                }
+                if !baseline_report.fixed.is_empty() {
+                    print!("{}", fixed_baseline_notice(&baseline_report.fixed));
+                }
+                if baseline_report.baselined > 0 || !baseline_report.fixed.is_empty() {
+                    println!();
+                }
+
                 // Human-readable output (existing logic)
-                if validation_result.is_valid() {
+                if validation_result.is_valid() && diagnostic_errors > 0 {
+                    let elapsed = start_time.elapsed();
+                    println!(
+                        "❌ Validation failed after {elapsed:.2?}: {diagnostic_errors} GraphQL document{} couldn't be analyzed",
+                        if diagnostic_errors == 1 { "" } else { "s" }
+                    );
+                    std::process::exit(1);
+                } else if validation_result.is_valid() {
                     let elapsed = start_time.elapsed();
                     println!("✅ All GraphQL queries pass validation! (took {elapsed:.2?})");
                     println!(
                         "Found {} queries and {} fragments",
-                        registry.queries.len(),
-                        registry.fragments.len()
+                        registry.query_count(),
+                        registry.fragment_count()
                     );
                 } else {
                     for error in &validation_result.errors {
@@ -86,9 +319,10 @@ fn main() -> anyhow::Result<()> {
 
                     // Use the same hint message as JSON output for consistency
                     let json_result: JsonValidationResult = validation_result.clone().into();
-                    println!("{}", json_result.hint);
-
-                    println!();
+                    if !json_result.hint.is_empty() {
+                        println!("{}", json_result.hint);
+                        println!();
+                    }
                     println!("❌ Validation failed after {elapsed:.2?}!");
                     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                     println!(
@@ -99,8 +333,8 @@ fn main() -> anyhow::Result<()> {
                         } else {
                             "s"
                         },
-                        registry.queries.len(),
-                        registry.fragments.len()
+                        registry.query_count(),
+                        registry.fragment_count()
                     );
                     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                     std::process::exit(1);
@@ -110,9 +344,9 @@ fn main() -> anyhow::Result<()> {
             if show_trees {
                 println!("\n--- Query Registry ---");
                 for query_entry in registry.queries.iter() {
-                    let query_name = query_entry.key();
-                    let query = query_entry.value();
-                    println!("Query: {} ({})", query_name, query.file_path.display());
+                    for query in query_entry.value() {
+                        println!("Query: {} ({})", query.name, query.file_path.display());
+                    }
                 }
             }
         }
@@ -125,3 +359,16 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// Baseline entries that no longer occur, shared by the text output and the report formats
+fn fixed_baseline_notice(fixed: &[BaselineEntry]) -> String {
+    let mut notice = format!(
+        "🎉 {} baseline entr{} fixed, rerun with --write-baseline to shrink the baseline:\n",
+        fixed.len(),
+        if fixed.len() == 1 { "y is" } else { "ies are" }
+    );
+    for entry in fixed {
+        notice.push_str(&format!("  - {entry}\n"));
+    }
+    notice
+}
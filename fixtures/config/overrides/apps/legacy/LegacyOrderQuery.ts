@@ -0,0 +1,13 @@
+import { graphql } from 'react-relay';
+
+// Unprotected directives of this file are downgraded to warnings,
+// the spread SharedPrice fragment lives outside apps/legacy and still fails
+export const LEGACY_ORDER_QUERY = graphql`
+  query LegacyOrderQuery {
+    order {
+      id @required(action: THROW)
+      status @throwOnFieldError
+      ...SharedPrice
+    }
+  }
+`;
@@ -0,0 +1,7 @@
+import { graphql } from 'react-relay';
+
+export const CHECKOUT_UNUSED = graphql`
+  fragment CheckoutUnused on Order {
+    id
+  }
+`;
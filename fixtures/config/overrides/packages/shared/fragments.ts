@@ -0,0 +1,14 @@
+import { graphql } from 'react-relay';
+
+export const SHARED_PRICE = graphql`
+  fragment SharedPrice on Order {
+    total @throwOnFieldError
+  }
+`;
+
+// Unused fragments are turned off outside of packages/checkout
+export const SHARED_UNUSED = graphql`
+  fragment SharedUnused on Order {
+    id
+  }
+`;
@@ -0,0 +1,9 @@
+import { graphql } from 'react-relay';
+
+export const USER_QUERY = graphql`
+  query ConfigPackageJsonUserQuery {
+    user @catch {
+      name @throwOnFieldError
+    }
+  }
+`;
@@ -0,0 +1,9 @@
+import { graphql } from 'react-relay';
+
+export const USER_QUERY = graphql`
+  query ConfigTomlUserQuery {
+    user @catch {
+      name @throwOnFieldError
+    }
+  }
+`;
@@ -0,0 +1,14 @@
+import { gql } from '@apollo/client';
+import { CircularFields } from './circular_reexports_peer';
+
+export { CircularFields };
+
+// Neither module defines CircularFields, the template is reported as dynamic
+export const GET_CIRCULAR_REEXPORT = gql`
+  query GetCircularReexport @catch {
+    viewer {
+      ...CircularFields
+    }
+  }
+  ${CircularFields}
+`;
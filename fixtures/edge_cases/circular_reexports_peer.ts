@@ -0,0 +1,3 @@
+import { CircularFields } from './circular_reexports';
+
+export { CircularFields };
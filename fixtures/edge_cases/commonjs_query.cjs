@@ -0,0 +1,9 @@
+const { gql } = require('@apollo/client');
+
+module.exports.COMMONJS_QUERY = gql`
+  query CommonJsQuery @catch {
+    settings {
+      theme @throwOnFieldError
+    }
+  }
+`;
@@ -0,0 +1,39 @@
+import { graphql } from 'react-relay';
+
+// Copy of the query in additional-queries.ts - only one of them would be validated
+export const COPIED_QUERY = graphql`
+  query AdditionalQueryEdgeCase($id: ID!) {
+    user(id: $id) {
+      email @throwOnFieldError
+    }
+  }
+`;
+
+export const FIRST_ACCOUNT_FRAGMENT = graphql`
+  fragment DuplicateAccountFragment on Account {
+    id
+  }
+`;
+
+export const SECOND_ACCOUNT_FRAGMENT = graphql`
+  fragment DuplicateAccountFragment on Account {
+    plan @throwOnFieldError
+  }
+`;
+
+// Anonymous operations are told apart by their location
+export const FIRST_ANONYMOUS = graphql`
+  {
+    viewer {
+      name @throwOnFieldError
+    }
+  }
+`;
+
+export const SECOND_ANONYMOUS = graphql`
+  {
+    viewer {
+      avatar @throwOnFieldError
+    }
+  }
+`;
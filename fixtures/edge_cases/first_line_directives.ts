@@ -0,0 +1,11 @@
+import { graphql } from 'react-relay';
+
+// Directives on the first template line are reported at their column in this file
+export const INLINE_FRAGMENT = graphql`fragment InlineAvatarFields on User { avatar @throwOnFieldError }`;
+
+export const FIRST_LINE_QUERY = graphql`query FirstLineQuery { viewer { name @throwOnFieldError }
+  user { id }
+}`;
+
+// Fragment spliced into the middle of the first line
+export const SPLICED_QUERY = graphql`${INLINE_FRAGMENT} query SplicedQuery { viewer { email @throwOnFieldError ...InlineAvatarFields } }`;
@@ -0,0 +1,10 @@
+import { graphql } from 'react-relay';
+
+// Missing closing brace - must not silently skip validation of this query
+export const BROKEN_QUERY = graphql`
+  query BrokenSyntaxQuery {
+    viewer {
+      id
+      name @throwOnFieldError
+  }
+`;
@@ -0,0 +1,25 @@
+import { graphql } from 'react-relay';
+import { gql } from './markdown';
+
+// Imported from a GraphQL module - always extracted
+export const RELAY_QUERY = graphql`
+  query ImportAwareRelayQuery @catch {
+    viewer {
+      id
+      name @throwOnFieldError
+    }
+  }
+`;
+
+// Markdown helper that happens to be called `gql` - foreign in import-aware mode
+export const CHANGELOG = gql`
+  # Changelog
+  - Added import-aware tag detection
+`;
+
+// Parameter shadows the imported `graphql` tag
+export function renderDocs(graphql: (strings: TemplateStringsArray) => string) {
+  return graphql`
+    ## Usage
+  `;
+}
@@ -0,0 +1,35 @@
+import { graphql } from './graphql';
+
+// Documents marked for the GraphQL editor extension instead of a tag
+export const MAGIC_COMMENT_FRAGMENT = /* GraphQL */ `
+  fragment MagicCommentFragment on User {
+    id
+    bio @throwOnFieldError
+  }
+`;
+
+export const MAGIC_COMMENT_QUERY = /* GraphQL */ `
+  query MagicCommentQuery {
+    viewer {
+      ...MagicCommentFragment
+    }
+  }
+`;
+
+export const MAGIC_COMMENT_STRING = /* GraphQL */ 'query MagicCommentStringQuery @catch { viewer { id } }';
+
+// Interpolated constants resolve like tagged ones
+export const MAGIC_COMMENT_COMPOSED = /* GraphQL */ `
+  query MagicCommentComposedQuery @catch {
+    viewer {
+      ...MagicCommentFragment
+    }
+  }
+  ${MAGIC_COMMENT_FRAGMENT}
+`;
+
+// Call-style documents carrying the comment are only extracted once
+export const MAGIC_COMMENT_CALL = graphql(/* GraphQL */ `fragment MagicCommentCallFragment on User { id }`);
+
+// Other comments don't mark GraphQL
+export const NOT_GRAPHQL = /* SQL */ `SELECT * FROM users`;
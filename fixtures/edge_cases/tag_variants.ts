@@ -0,0 +1,37 @@
+import { graphql } from 'react-relay';
+import { gql } from '@apollo/client';
+import Relay from 'react-relay/classic';
+
+// Member tag of a configured name
+export const EXPERIMENTAL_QUERY = gql.experimental`
+  query ExperimentalTagQuery @catch {
+    user {
+      id
+      name @throwOnFieldError
+    }
+  }
+`;
+
+// gql.tada / codegen client preset call style with a template argument
+export const CALL_QUERY = graphql(`
+  query CallStyleQuery {
+    viewer {
+      id
+      email @throwOnFieldError
+    }
+  }
+`);
+
+// Call style with a plain string argument
+export const STRING_FRAGMENT = graphql("fragment CallStyleFragment on User { id avatar @throwOnFieldError }");
+
+// Only extracted when `Relay.QL` is configured via --tag
+export const LEGACY_FRAGMENT = Relay.QL`
+  fragment LegacyRelayFragment on User {
+    id
+    legacyField @throwOnFieldError
+  }
+`;
+
+// Unrelated calls are ignored
+export const notGraphQL = String.raw`query NotGraphQL { id }`;
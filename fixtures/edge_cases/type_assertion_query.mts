@@ -0,0 +1,12 @@
+import { gql } from '@apollo/client';
+import type { DocumentNode } from 'graphql';
+
+// Angle-bracket type assertions are only valid without JSX
+export const ASSERTED_QUERY = <DocumentNode>gql`
+  query TypeAssertionQuery {
+    user {
+      id
+      status @throwOnFieldError
+    }
+  }
+`;
@@ -0,0 +1,11 @@
+import { graphql } from 'react-relay';
+
+export const QUERY_IN_BROKEN_FILE = graphql`
+  query QueryInBrokenFile {
+    viewer {
+      id
+    }
+  }
+`;
+
+export function broken( {
@@ -0,0 +1,29 @@
+import { graphql } from 'react-relay';
+
+// Misspelled spread of `UserInfo` from required_variants.ts
+export const TYPO_QUERY = graphql`
+  query UnknownFragmentTypoQuery($id: ID!) {
+    user(id: $id) {
+      ...UserInfoo
+    }
+  }
+`;
+
+// Fragment from a file outside the scanned pattern
+export const MISSING_QUERY = graphql`
+  query UnknownFragmentMissingQuery($id: ID!) {
+    user(id: $id) {
+      id
+      ...CompletelyUnrelatedProfileFields
+    }
+  }
+`;
+
+// Misspelled spreads are reported inside protected subtrees as well
+export const PROTECTED_QUERY = graphql`
+  query UnknownFragmentProtectedQuery($id: ID!) {
+    user(id: $id) @catch {
+      ...UserInfoo
+    }
+  }
+`;
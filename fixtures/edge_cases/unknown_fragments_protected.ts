@@ -0,0 +1,41 @@
+import { graphql } from 'react-relay';
+
+export const USER_FIELDS_FRAGMENT = graphql`
+  fragment UserFields on User {
+    id
+    name
+  }
+`;
+
+// Typo under @catch, the fragment can't be resolved however well it is protected
+export const PROTECTED_TYPO_QUERY = graphql`
+  query ProtectedTypoQuery {
+    user @catch {
+      ...UserFeilds
+    }
+  }
+`;
+
+// Fragment only reached through a protected spread
+export const PROTECTED_SPREAD_FRAGMENT = graphql`
+  fragment ProtectedSpreadFields on User {
+    friends {
+      ...UserFeilds
+    }
+  }
+`;
+
+export const PROTECTED_SPREAD_QUERY = graphql`
+  query ProtectedSpreadQuery {
+    user {
+      ...ProtectedSpreadFields @catch
+    }
+  }
+`;
+
+// Fragment no operation uses
+export const UNUSED_TYPO_FRAGMENT = graphql`
+  fragment UnusedTypoFields on User {
+    ...UserFeilds
+  }
+`;
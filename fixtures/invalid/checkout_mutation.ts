@@ -0,0 +1,33 @@
+import { graphql } from 'react-relay';
+
+export const CheckoutPaymentFragment = graphql`
+  fragment CheckoutMutation_payment on Checkout {
+    paymentMethod @required(action: THROW)
+  }
+`;
+
+export const ConfirmCheckout = graphql`
+  mutation ConfirmCheckoutMutation($id: ID!) {
+    confirmCheckout(id: $id) {
+      checkout {
+        ...CheckoutMutation_payment
+      }
+    }
+  }
+`;
+
+export const CartUpdates = graphql`
+  subscription {
+    cartUpdated {
+      itemCount @throwOnFieldError
+    }
+  }
+`;
+
+export const Viewer = graphql`
+  {
+    viewer {
+      name @throwOnFieldError
+    }
+  }
+`;
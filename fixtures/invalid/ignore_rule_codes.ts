@@ -0,0 +1,16 @@
+import { graphql } from 'react-relay';
+
+// Ignore comments listing rule codes only silence the matching directives
+// `email` stays unprotected because GQS002 only covers @required(action: THROW)
+export const IGNORE_RULE_CODES_QUERY = graphql`
+  query IgnoreRuleCodesQuery {
+    user {
+      # gql-safeguard-ignore GQS002
+      name @required(action: THROW)
+      # gql-safeguard-ignore GQS002
+      email @throwOnFieldError
+      # gql-safeguard-ignore GQS001, GQS002
+      avatar @throwOnFieldError @required(action: THROW)
+    }
+  }
+`;
@@ -0,0 +1,11 @@
+import { gql } from '@apollo/client';
+import { SHARED_ORDER_TOTAL } from '../shared/fragments';
+
+export const GET_UNPROTECTED_SHARED_ORDER = gql`
+  query GetUnprotectedSharedOrder($id: ID!) {
+    order(id: $id) {
+      ...SharedOrderTotal
+    }
+  }
+  ${SHARED_ORDER_TOTAL}
+`;
@@ -0,0 +1,17 @@
+import { gql } from '@apollo/client';
+
+export const GET_INTERPOLATED_ORDER = gql`
+  query GetInterpolatedOrder($id: ID!) {
+    order(id: $id) {
+      id
+      ...InterpolatedOrderTotal
+    }
+  }
+  ${ORDER_TOTAL}
+`;
+
+const ORDER_TOTAL = gql`
+  fragment InterpolatedOrderTotal on Order {
+    total @throwOnFieldError
+  }
+`;
@@ -0,0 +1,17 @@
+import React from 'react';
+import { graphql, useLazyLoadQuery } from 'react-relay';
+
+// Plain `.js` files in older packages still contain JSX
+const legacyQuery = graphql`
+  query LegacyModuleQuery {
+    viewer {
+      id
+      lastLogin @throwOnFieldError
+    }
+  }
+`;
+
+export default function LegacyModule() {
+  const data = useLazyLoadQuery(legacyQuery, {});
+  return <div>{data.viewer.lastLogin}</div>;
+}
@@ -0,0 +1,2 @@
+export * from './profile';
+export { ORDER_TOTAL_FRAGMENT as SHARED_ORDER_TOTAL } from './order';
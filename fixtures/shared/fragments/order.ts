@@ -0,0 +1,7 @@
+import { gql } from '@apollo/client';
+
+export const ORDER_TOTAL_FRAGMENT = gql`
+  fragment SharedOrderTotal on Order {
+    total @throwOnFieldError
+  }
+`;
@@ -0,0 +1,9 @@
+import { gql } from '@apollo/client';
+
+const PROFILE_FRAGMENT = gql`
+  fragment SharedProfile on User @catch {
+    name @throwOnFieldError
+  }
+`;
+
+export { PROFILE_FRAGMENT as SHARED_PROFILE };
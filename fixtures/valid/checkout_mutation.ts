@@ -0,0 +1,26 @@
+import { graphql } from 'react-relay';
+
+export const CheckoutTotalFragment = graphql`
+  fragment CheckoutMutation_total on Checkout {
+    total @required(action: THROW)
+    currency
+  }
+`;
+
+export const CompleteCheckout = graphql`
+  mutation CompleteCheckoutMutation($id: ID!) {
+    completeCheckout(id: $id) @catch {
+      checkout {
+        ...CheckoutMutation_total
+      }
+    }
+  }
+`;
+
+export const OrderUpdates = graphql`
+  subscription OrderUpdatesSubscription @catch {
+    orderUpdated {
+      status @throwOnFieldError
+    }
+  }
+`;
@@ -0,0 +1,21 @@
+import { gql } from '@apollo/client';
+import { SHARED_PROFILE } from '../shared/fragments';
+import { ORDER_TOTAL_FRAGMENT } from '@shared/order';
+
+export const GET_SHARED_PROFILE = gql`
+  query GetSharedProfile($id: ID!) {
+    user(id: $id) {
+      ...SharedProfile
+    }
+  }
+  ${SHARED_PROFILE}
+`;
+
+export const GET_SHARED_ORDER = gql`
+  query GetSharedOrder($id: ID!) {
+    order(id: $id) @catch {
+      ...SharedOrderTotal
+    }
+  }
+  ${ORDER_TOTAL_FRAGMENT}
+`;
@@ -0,0 +1,25 @@
+import { gql } from '@apollo/client';
+
+const PROFILE_AVATAR = gql`
+  fragment InterpolatedAvatar on User @throwOnFieldError {
+    avatarUrl
+  }
+`;
+
+const PROFILE_DETAILS = gql`
+  fragment InterpolatedProfile on User @catch {
+    name
+    ...InterpolatedAvatar
+  }
+  ${PROFILE_AVATAR}
+`;
+
+export const GET_INTERPOLATED_PROFILE = gql`
+  query GetInterpolatedProfile($id: ID!) {
+    user(id: $id) {
+      id
+      ...InterpolatedProfile
+    }
+  }
+  ${PROFILE_DETAILS}
+`;
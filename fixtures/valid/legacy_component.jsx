@@ -0,0 +1,14 @@
+import React from 'react';
+import { graphql, useFragment } from 'react-relay';
+
+const legacyUserFragment = graphql`
+  fragment LegacyComponent_user on User @catch {
+    id
+    nickname @throwOnFieldError
+  }
+`;
+
+export function LegacyComponent({ user }) {
+  const data = useFragment(legacyUserFragment, user);
+  return <span>{data.ok ? data.value.nickname : null}</span>;
+}
@@ -0,0 +1,224 @@
+//! Baseline of accepted violations so only new ones fail validation
+//!
+//! Violations are recorded by a fingerprint of their rule code, operation, fragment and
+//! location path. Line numbers aren't part of it, so unrelated edits above a violation
+//! don't turn it into a new one.
+
+use anyhow::{bail, Context, Result};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+// The readable fields are only kept for reviewing baseline diffs, matching uses the fingerprint
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub code: String,
+    pub operation: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    // Identical violations (e.g. in anonymous operations of the same kind) share a fingerprint
+    pub count: usize,
+}
+
+// Outcome of comparing a validation result against the baseline
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineReport {
+    // Errors removed from the result because the baseline accepts them
+    pub baselined: usize,
+    // Entries (or the part of their count) that no longer occur
+    pub fixed: Vec<BaselineEntry>,
+}
+
+// Stable across runs, platforms and Rust versions (FNV-1a)
+pub fn fingerprint(error: &ValidationError) -> String {
+    let key = format!(
+        "{}\u{0}{}\u{0}{}\u{0}{}",
+        error.error_type.code(),
+        error.context.query_name,
+        error.context.fragment_name.as_deref().unwrap_or_default(),
+        error.context.location_path
+    );
+    hash(&key)
+}
+
+// FNV-1a hex digest
+pub(crate) fn hash(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+impl Baseline {
+    // Accepts every error of the result, warnings never fail and aren't recorded
+    pub fn from_result(result: &ValidationResult) -> Self {
+        let mut entries: FxHashMap<String, BaselineEntry> = FxHashMap::default();
+        for error in &result.errors {
+            let fingerprint = fingerprint(error);
+            entries
+                .entry(fingerprint.clone())
+                .or_insert_with(|| BaselineEntry {
+                    fingerprint,
+                    code: error.error_type.code().to_string(),
+                    operation: error.context.query_name.clone(),
+                    fragment: error.context.fragment_name.clone(),
+                    path: error.context.location_path.clone(),
+                    count: 0,
+                })
+                .count += 1;
+        }
+
+        // Sorted so regenerating the baseline gives minimal diffs
+        let mut entries: Vec<BaselineEntry> = entries.into_values().collect();
+        entries.sort();
+        Baseline {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        Self::parse(&content).with_context(|| format!("Invalid baseline {}", path.display()))
+    }
+
+    // Fingerprints of another version don't match, every violation would look new
+    fn parse(content: &str) -> Result<Self> {
+        let baseline: Baseline = serde_json::from_str(content)?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "baseline version {} is not supported (expected {BASELINE_VERSION}), rerun with --write-baseline to regenerate it",
+                baseline.version
+            );
+        }
+        Ok(baseline)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        fs::write(path, content + "\n")
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+
+    // Removes the accepted errors from the result
+    pub fn apply(&self, result: &mut ValidationResult) -> BaselineReport {
+        let mut remaining: FxHashMap<&str, usize> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.fingerprint.as_str(), entry.count))
+            .collect();
+
+        let errors_before = result.errors.len();
+        result.errors.retain(
+            |error| match remaining.get_mut(fingerprint(error).as_str()) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            },
+        );
+
+        let fixed = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let count = remaining[entry.fingerprint.as_str()];
+                (count > 0).then(|| BaselineEntry {
+                    count,
+                    ..entry.clone()
+                })
+            })
+            .collect();
+
+        BaselineReport {
+            baselined: errors_before - result.errors.len(),
+            fixed,
+        }
+    }
+}
+
+impl std::fmt::Display for BaselineEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code, self.operation)?;
+        if let Some(fragment) = &self.fragment {
+            write!(f, " ({fragment})")?;
+        }
+        if !self.path.is_empty() {
+            write!(f, " at {}", self.path)?;
+        }
+        if self.count > 1 {
+            write!(f, " ×{}", self.count)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::process_files;
+    use crate::test_utils::fixture_files;
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_baseline_entries() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/missing_catch.tsx",
+            "invalid/partial_protection.ts",
+        ]));
+        let baseline = Baseline::from_result(&validate_registry(&registry));
+        insta::assert_snapshot!(serde_json::to_string_pretty(&baseline).unwrap());
+    }
+
+    #[test]
+    fn test_apply_baseline() {
+        let registry = process_files(&fixture_files(&["invalid/missing_catch.tsx"]));
+        let mut baseline = Baseline::from_result(&validate_registry(&registry));
+
+        // Fingerprints ignore line numbers
+        let mut result = validate_registry(&registry);
+        for error in &mut result.errors {
+            error.context.line = error.context.line.map(|line| line + 10);
+        }
+        let report = baseline.apply(&mut result);
+        assert!(result.errors.is_empty());
+        assert_eq!(
+            report.baselined,
+            baseline.entries.iter().map(|e| e.count).sum::<usize>()
+        );
+        assert!(report.fixed.is_empty());
+
+        // Entries that no longer occur are reported as fixed, new violations stay
+        baseline.entries[0].fingerprint = "0000000000000000".to_string();
+        let mut result = validate_registry(&registry);
+        let report = baseline.apply(&mut result);
+        assert_eq!(result.errors.len(), baseline.entries[0].count);
+        assert_eq!(report.fixed.len(), 1);
+        assert_eq!(report.fixed[0].fingerprint, "0000000000000000");
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let baseline = Baseline::parse(r#"{ "version": 1, "entries": [] }"#).unwrap();
+        assert!(baseline.entries.is_empty());
+
+        let error = Baseline::parse(r#"{ "version": 2, "entries": [] }"#).unwrap_err();
+        assert!(error.to_string().contains("version 2"));
+    }
+}
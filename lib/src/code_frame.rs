@@ -0,0 +1,65 @@
+//! Excerpt of a source file around a finding, with a caret under the reported column
+//!
+//! ```text
+//!    4 |   fragment UserFields on User {
+//!    5 |     id
+//! >  6 |     name @throwOnFieldError
+//!      |          ^
+//!    7 |     email
+//! ```
+
+// Lines shown above and below the reported line
+const CONTEXT_LINES: u32 = 2;
+
+// `line` and `col` are 1-based, without a column only the line is marked
+pub fn code_frame(source: &str, line: u32, col: Option<u32>) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line as usize > lines.len() {
+        return String::new();
+    }
+
+    let first = line.saturating_sub(CONTEXT_LINES).max(1);
+    let last = (line + CONTEXT_LINES).min(lines.len() as u32);
+    let gutter_width = last.to_string().len();
+
+    let mut frame = String::new();
+    for number in first..=last {
+        let content = lines[number as usize - 1].trim_end();
+        let marker = if number == line { '>' } else { ' ' };
+        // Empty lines don't leave trailing whitespace after the gutter
+        let row = format!("{marker} {number:>gutter_width$} | {content}");
+        frame.push_str(row.trim_end());
+        frame.push('\n');
+
+        if let Some(col) = col.filter(|_| number == line) {
+            // Tabs are kept so the caret lines up however wide the terminal renders them
+            let padding: String = content
+                .chars()
+                .take(col.saturating_sub(1) as usize)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            frame.push_str(&format!("  {:gutter_width$} | {padding}^\n", ""));
+        }
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_frame() {
+        let source = "const USER_FRAGMENT = gql`\n  fragment UserFields on User {\n\n    id\n\tname @throwOnFieldError\n  }\n`;\n";
+
+        let mut frames = String::new();
+        frames.push_str(&code_frame(source, 5, Some(7)));
+        frames.push_str("---\n");
+        // Near the start of the file and without a column
+        frames.push_str(&code_frame(source, 1, None));
+        frames.push_str("---\n");
+        // Positions outside of the file render nothing
+        frames.push_str(&code_frame(source, 42, Some(1)));
+        insta::assert_snapshot!(frames);
+    }
+}
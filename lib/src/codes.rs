@@ -0,0 +1,94 @@
+//! Rule codes like `GQS001` for every finding
+//!
+//! Shared by validation, the config (`rules`) and ignore comments, which are evaluated while
+//! parsing before any validation runs.
+
+use crate::diagnostics::Severity;
+
+// Validation error types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationErrorType {
+    // @throwOnFieldError without a @catch above it
+    UnprotectedThrowOnFieldError,
+    // @required(action: THROW) without a @catch above it
+    UnprotectedRequiredThrow,
+    // Same operation name defined in several places (Relay and persisted queries need unique names)
+    DuplicateOperationName,
+    // Same fragment name defined in several places, spreads can't tell which one is meant
+    DuplicateFragmentName,
+    // Spread of a fragment that isn't in the registry (typo or file outside the scanned pattern)
+    UnknownFragment,
+    // Fragments spreading each other in a loop
+    FragmentCycle,
+    // Fragment that no operation spreads (directly or through other fragments)
+    UnusedFragment,
+    // Unused fragment containing throwing directives that are therefore never checked
+    UnreachableThrow,
+}
+
+impl ValidationErrorType {
+    // Every finding in code order
+    pub const ALL: [ValidationErrorType; 8] = [
+        ValidationErrorType::UnprotectedThrowOnFieldError,
+        ValidationErrorType::UnprotectedRequiredThrow,
+        ValidationErrorType::DuplicateOperationName,
+        ValidationErrorType::DuplicateFragmentName,
+        ValidationErrorType::UnknownFragment,
+        ValidationErrorType::FragmentCycle,
+        ValidationErrorType::UnusedFragment,
+        ValidationErrorType::UnreachableThrow,
+    ];
+
+    // Stable rule code shown in every output format and accepted by ignore comments
+    // Codes are never reused - new rules get the next free number
+    pub fn code(&self) -> &'static str {
+        match self {
+            ValidationErrorType::UnprotectedThrowOnFieldError => "GQS001",
+            ValidationErrorType::UnprotectedRequiredThrow => "GQS002",
+            ValidationErrorType::DuplicateOperationName => "GQS003",
+            ValidationErrorType::DuplicateFragmentName => "GQS004",
+            ValidationErrorType::UnknownFragment => "GQS005",
+            ValidationErrorType::FragmentCycle => "GQS006",
+            ValidationErrorType::UnusedFragment => "GQS007",
+            ValidationErrorType::UnreachableThrow => "GQS008",
+        }
+    }
+
+    // Finding for a code like `GQS001` (case-insensitive)
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|error_type| error_type.code().eq_ignore_ascii_case(code))
+    }
+
+    // Default severity - findings about dead code are reported without failing validation
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationErrorType::UnusedFragment | ValidationErrorType::UnreachableThrow => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationErrorType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationErrorType::UnprotectedThrowOnFieldError => {
+                write!(f, "Unprotected @throwOnFieldError")
+            }
+            ValidationErrorType::UnprotectedRequiredThrow => {
+                write!(f, "Unprotected @required(action: THROW)")
+            }
+            ValidationErrorType::DuplicateOperationName => write!(f, "Duplicate operation name"),
+            ValidationErrorType::DuplicateFragmentName => write!(f, "Duplicate fragment name"),
+            ValidationErrorType::UnknownFragment => write!(f, "Unknown fragment"),
+            ValidationErrorType::FragmentCycle => write!(f, "Circular fragment reference"),
+            ValidationErrorType::UnusedFragment => write!(f, "Unused fragment"),
+            ValidationErrorType::UnreachableThrow => {
+                write!(f, "Unchecked throwing directive in unused fragment")
+            }
+        }
+    }
+}
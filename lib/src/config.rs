@@ -0,0 +1,450 @@
+//! Project configuration from `gql-safeguard.toml` or the `gqlSafeguard` key of `package.json`
+//!
+//! The first directory from the working directory upward containing either file provides the
+//! configuration. Every setting is optional and CLI flags take precedence over it.
+
+use anyhow::{anyhow, bail, Context, Result};
+use globset::{Glob, GlobSetBuilder};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::codes::ValidationErrorType;
+use crate::diagnostics::Severity;
+use crate::parsers::graphql_parser::IgnorePolicy;
+use crate::rules::{RuleOverride, RuleSet};
+
+pub const CONFIG_FILE_NAME: &str = "gql-safeguard.toml";
+pub const PACKAGE_JSON_KEY: &str = "gqlSafeguard";
+
+// Same keys in TOML and package.json, e.g. `failOnDynamic = true`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Config {
+    // Globs of files to scan
+    pub include: Option<Vec<String>>,
+    // Globs of directories to skip
+    pub exclude: Option<Vec<String>>,
+    pub tags: Option<Vec<String>>,
+    pub import_aware: Option<bool>,
+    pub tag_modules: Option<Vec<String>>,
+    pub fail_on_dynamic: Option<bool>,
+    pub fail_on_parse_error: Option<bool>,
+    pub format: Option<OutputFormat>,
+    // "any", "codes" (ignore comments must list rule codes) or "off"
+    pub ignore_comments: Option<IgnorePolicy>,
+    // Level per rule code, e.g. `GQS007 = "off"`
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleLevel>,
+    // Rule levels for parts of the project, applied in order
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
+}
+
+// `[[overrides]]` table, globs are relative to the directory of the config file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConfigOverride {
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleLevel>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Sarif,
+    Github,
+    Codeclimate,
+    Junit,
+    Checkstyle,
+    Compact,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "sarif" => Ok(OutputFormat::Sarif),
+            "github" => Ok(OutputFormat::Github),
+            "codeclimate" => Ok(OutputFormat::Codeclimate),
+            "junit" => Ok(OutputFormat::Junit),
+            "checkstyle" => Ok(OutputFormat::Checkstyle),
+            "compact" => Ok(OutputFormat::Compact),
+            _ => Err(format!(
+                "unknown format `{format}`, expected text, compact, json, sarif, github, codeclimate, junit or checkstyle"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+// Configuration and the file it was read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadedConfig {
+    pub path: PathBuf,
+    pub config: Config,
+}
+
+impl LoadedConfig {
+    // Directory containing the configuration file
+    pub fn directory(&self) -> &Path {
+        self.path.parent().unwrap_or(Path::new("."))
+    }
+
+    // `include` globs anchored at the config directory, wherever the scan starts
+    pub fn include(&self) -> Result<Option<Vec<String>>> {
+        self.config
+            .include
+            .as_deref()
+            .map(|patterns| self.anchor(patterns))
+            .transpose()
+    }
+
+    // `exclude` globs anchored at the config directory
+    pub fn exclude(&self) -> Result<Option<Vec<String>>> {
+        self.config
+            .exclude
+            .as_deref()
+            .map(|patterns| self.anchor(patterns))
+            .transpose()
+    }
+
+    fn anchor(&self, patterns: &[String]) -> Result<Vec<String>> {
+        let directory = self
+            .directory()
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", self.directory().display()))?;
+        let directory = escape_glob(&directory.to_string_lossy());
+        Ok(patterns
+            .iter()
+            .map(|pattern| {
+                let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+                format!("{}/{pattern}", directory.trim_end_matches('/'))
+            })
+            .collect())
+    }
+}
+
+// Directory names may contain glob syntax like `[id]` or `{a,b}`
+fn escape_glob(path: &str) -> String {
+    let mut escaped = String::with_capacity(path.len());
+    for c in path.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '{' | '}') {
+            escaped.push('[');
+            escaped.push(c);
+            escaped.push(']');
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+impl Config {
+    // Closest configuration in `start` or one of its ancestors
+    pub fn discover(start: &Path) -> Result<Option<LoadedConfig>> {
+        let start = start
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve {}", start.display()))?;
+
+        for directory in start.ancestors() {
+            let config_path = directory.join(CONFIG_FILE_NAME);
+            if config_path.is_file() {
+                return Self::load(&config_path).map(Some);
+            }
+
+            // package.json files without the key or that aren't valid JSON don't stop the search
+            let package_json = directory.join("package.json");
+            if package_json.is_file() {
+                let Ok(package) = read_package_json(&package_json) else {
+                    continue;
+                };
+                if let Some(config) = Self::from_package(package, &package_json)? {
+                    return Ok(Some(LoadedConfig {
+                        path: package_json,
+                        config,
+                    }));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Reads a `gql-safeguard.toml` or the `gqlSafeguard` key of a `package.json`
+    pub fn load(path: &Path) -> Result<LoadedConfig> {
+        let config = if path.file_name().is_some_and(|name| name == "package.json") {
+            Self::from_package_json(path)?
+                .ok_or_else(|| anyhow!("{} has no `{PACKAGE_JSON_KEY}` key", path.display()))?
+        } else {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            toml::from_str(&content)
+                .with_context(|| format!("Invalid configuration in {}", path.display()))?
+        };
+
+        Ok(LoadedConfig {
+            path: path.to_path_buf(),
+            config,
+        })
+    }
+
+    fn from_package_json(path: &Path) -> Result<Option<Config>> {
+        Self::from_package(read_package_json(path)?, path)
+    }
+
+    fn from_package(mut package: serde_json::Value, path: &Path) -> Result<Option<Config>> {
+        match package
+            .get_mut(PACKAGE_JSON_KEY)
+            .map(serde_json::Value::take)
+        {
+            Some(value) => serde_json::from_value(value).map(Some).with_context(|| {
+                format!(
+                    "Invalid `{PACKAGE_JSON_KEY}` configuration in {}",
+                    path.display()
+                )
+            }),
+            None => Ok(None),
+        }
+    }
+
+    // Built-in rules with the configured levels applied, `root` is the directory of the config file
+    pub fn rule_set(&self, root: &Path) -> Result<RuleSet> {
+        let mut rules = RuleSet::default();
+        for (error_type, level) in rule_levels(&self.rules)? {
+            match level {
+                RuleLevel::Off => rules.disable(error_type),
+                RuleLevel::Warning => rules.set_severity(error_type, Severity::Warning),
+                RuleLevel::Error => rules.set_severity(error_type, Severity::Error),
+            }
+        }
+
+        // Symlinks and `..` are resolved like for the include/exclude globs
+        let root = root
+            .canonicalize()
+            .or_else(|_| std::path::absolute(root))
+            .with_context(|| format!("Failed to resolve {}", root.display()))?;
+        for config_override in &self.overrides {
+            let mut files = GlobSetBuilder::new();
+            for pattern in &config_override.files {
+                files.add(
+                    Glob::new(pattern)
+                        .with_context(|| format!("Invalid override glob `{pattern}`"))?,
+                );
+            }
+            let mut rule_override = RuleOverride::new(root.clone(), files.build()?);
+            for (error_type, level) in rule_levels(&config_override.rules)? {
+                match level {
+                    RuleLevel::Off => rule_override.disable(error_type),
+                    RuleLevel::Warning => rule_override.set_severity(error_type, Severity::Warning),
+                    RuleLevel::Error => rule_override.set_severity(error_type, Severity::Error),
+                }
+            }
+            rules.add_override(rule_override);
+        }
+
+        Ok(rules)
+    }
+}
+
+fn read_package_json(path: &Path) -> Result<serde_json::Value> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid JSON in {}", path.display()))
+}
+
+fn rule_levels(
+    levels: &BTreeMap<String, RuleLevel>,
+) -> Result<Vec<(ValidationErrorType, RuleLevel)>> {
+    levels
+        .iter()
+        .map(|(code, level)| match ValidationErrorType::from_code(code) {
+            Some(error_type) => Ok((error_type, *level)),
+            None => bail!("Unknown rule code `{code}` in configuration"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::process_glob;
+    use crate::test_utils::fixture_path;
+    use crate::validate_registry::validate_registry_with_rules;
+
+    #[test]
+    fn test_discover_configuration() {
+        // The TOML file wins over the package.json next to it, nested packages use their own key
+        let toml_config = Config::discover(&fixture_path("config/toml/src"))
+            .unwrap()
+            .unwrap();
+        let package_config = Config::discover(&fixture_path("config/package_json/src"))
+            .unwrap()
+            .unwrap();
+
+        let mut result = String::new();
+        for loaded in [toml_config, package_config] {
+            let relative_path = loaded
+                .path
+                .strip_prefix(fixture_path(""))
+                .unwrap()
+                .to_path_buf();
+            result.push_str(&format!(
+                "{}\n{:#?}\n",
+                relative_path.display(),
+                loaded.config
+            ));
+        }
+        insta::assert_snapshot!(result);
+    }
+
+    #[test]
+    fn test_discover_skips_malformed_package_json() {
+        let root = fixture_path("config/malformed_package_json");
+        let loaded = Config::discover(&root.join("src")).unwrap().unwrap();
+        assert_eq!(
+            loaded.path,
+            root.canonicalize().unwrap().join(CONFIG_FILE_NAME)
+        );
+
+        // Only a package.json chosen as the config source has to be valid
+        assert!(Config::load(&root.join("src/package.json")).is_err());
+    }
+
+    #[test]
+    fn test_overrides_apply_to_owning_file() {
+        let root = fixture_path("config/overrides");
+        let loaded = Config::load(&root.join(CONFIG_FILE_NAME)).unwrap();
+        let registry = process_glob(&root, &["**/*.ts"], &[]).unwrap();
+        let result =
+            validate_registry_with_rules(&registry, &loaded.config.rule_set(&root).unwrap());
+
+        let mut findings = String::new();
+        for error in result.errors.iter().chain(&result.warnings) {
+            findings.push_str(&format!(
+                "{:?} {} {} {}\n",
+                error.severity,
+                error.error_type.code(),
+                error.context.query_name,
+                error
+                    .context
+                    .owner_file()
+                    .strip_prefix(&root)
+                    .unwrap()
+                    .display()
+            ));
+        }
+        insta::assert_snapshot!(findings);
+    }
+
+    #[test]
+    fn test_overrides_match_unnormalized_scan_root() {
+        let root = fixture_path("config/overrides");
+        let loaded = Config::load(&root.join(CONFIG_FILE_NAME)).unwrap();
+        let rules = loaded.config.rule_set(&root).unwrap();
+
+        // `..` (like a symlink) must not stop the override globs from matching
+        let levels = |scan_root: PathBuf| {
+            let registry = process_glob(&scan_root, &["**/*.ts"], &[]).unwrap();
+            let result = validate_registry_with_rules(&registry, &rules);
+            let mut levels: Vec<String> = result
+                .errors
+                .iter()
+                .chain(&result.warnings)
+                .map(|error| format!("{:?} {}", error.severity, error.error_type.code()))
+                .collect();
+            levels.sort();
+            levels
+        };
+
+        assert_eq!(levels(root.join("packages/..")), levels(root.clone()));
+    }
+
+    #[test]
+    fn test_patterns_relative_to_config_directory() {
+        let root = fixture_path("config/overrides");
+        let mut loaded = Config::load(&root.join(CONFIG_FILE_NAME)).unwrap();
+        loaded.config.include = Some(vec!["packages/**/*.ts".to_string()]);
+        loaded.config.exclude = Some(vec!["./packages/shared".to_string()]);
+        let include = loaded.include().unwrap().unwrap();
+        let exclude = loaded.exclude().unwrap().unwrap();
+        let include: Vec<&str> = include.iter().map(String::as_str).collect();
+        let exclude: Vec<&str> = exclude.iter().map(String::as_str).collect();
+
+        // Scanning from a subdirectory doesn't change what the globs refer to
+        let scanned_files = |scan_root: PathBuf| {
+            let registry = process_glob(&scan_root, &include, &exclude).unwrap();
+            let mut files: Vec<String> = registry
+                .fragments
+                .iter()
+                .flat_map(|entry| entry.value().clone())
+                .map(|fragment| {
+                    let relative_path = fragment.file_path.strip_prefix(&root).unwrap();
+                    relative_path.to_string_lossy().replace('\\', "/")
+                })
+                .collect();
+            files.sort();
+            files.dedup();
+            files
+        };
+
+        assert_eq!(
+            scanned_files(root.clone()),
+            ["packages/checkout/CheckoutFragments.ts"]
+        );
+        assert_eq!(
+            scanned_files(root.join("packages")),
+            ["packages/checkout/CheckoutFragments.ts"]
+        );
+        assert!(scanned_files(root.join("apps")).is_empty());
+        let escaped = Glob::new(&format!("{}/*.ts", escape_glob("/app/[id]/{a,b}")))
+            .unwrap()
+            .compile_matcher();
+        assert!(escaped.is_match("/app/[id]/{a,b}/page.ts"));
+        assert!(!escaped.is_match("/app/i/a/page.ts"));
+    }
+
+    #[test]
+    fn test_rule_levels() {
+        let config: Config = toml::from_str(
+            r#"
+[rules]
+GQS001 = "warn"
+gqs007 = "off"
+GQS008 = "error"
+"#,
+        )
+        .unwrap();
+        let rules = config.rule_set(Path::new(".")).unwrap();
+
+        assert_eq!(
+            rules.severity(&ValidationErrorType::UnprotectedThrowOnFieldError),
+            Severity::Warning
+        );
+        assert_eq!(
+            rules.severity(&ValidationErrorType::UnreachableThrow),
+            Severity::Error
+        );
+        assert!(!rules.is_enabled(&ValidationErrorType::UnusedFragment));
+        assert!(rules.is_enabled(&ValidationErrorType::UnprotectedRequiredThrow));
+
+        let unknown: Config = toml::from_str("[rules]\nGQS999 = \"off\"").unwrap();
+        assert!(unknown.rule_set(Path::new(".")).is_err());
+    }
+}
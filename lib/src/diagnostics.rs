@@ -0,0 +1,256 @@
+//! Non-fatal findings collected while extracting GraphQL
+//!
+//! Diagnostics explain why something in a source file was not analyzed instead of
+//! silently dropping it.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticKind {
+    // Tag name is bound locally (variable, parameter, function) instead of imported
+    ShadowedTag,
+    // Tag name is imported from a module that isn't a configured GraphQL module
+    ForeignTag,
+    // `#import` of a GraphQL document that doesn't exist
+    UnresolvedImport,
+    // Template interpolating values that can't be resolved statically
+    DynamicTemplate,
+    // Source file or GraphQL document that couldn't be parsed (see `Phase`)
+    ParseError,
+    // Schema (SDL) document without operations or fragments to validate
+    SkippedSchema,
+}
+
+impl std::fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticKind::ShadowedTag => write!(f, "Shadowed GraphQL tag"),
+            DiagnosticKind::ForeignTag => write!(f, "Foreign GraphQL tag"),
+            DiagnosticKind::UnresolvedImport => write!(f, "Unresolved #import"),
+            DiagnosticKind::DynamicTemplate => write!(f, "Dynamic GraphQL template"),
+            DiagnosticKind::ParseError => write!(f, "Parse error"),
+            DiagnosticKind::SkippedSchema => write!(f, "Skipped schema document"),
+        }
+    }
+}
+
+impl DiagnosticKind {
+    // Same name as `kind` in the JSON output, used as rule id by the reports
+    pub fn code(&self) -> &'static str {
+        match self {
+            DiagnosticKind::ShadowedTag => "shadowedTag",
+            DiagnosticKind::ForeignTag => "foreignTag",
+            DiagnosticKind::UnresolvedImport => "unresolvedImport",
+            DiagnosticKind::DynamicTemplate => "dynamicTemplate",
+            DiagnosticKind::ParseError => "parseError",
+            DiagnosticKind::SkippedSchema => "skippedSchema",
+        }
+    }
+}
+
+// Pipeline stage a diagnostic comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Phase {
+    // Reading JS/TS sources, components and GraphQL documents
+    Extraction,
+    // Parsing the extracted GraphQL
+    GraphQL,
+}
+
+// Warnings are reported, errors also fail validation (e.g. dynamic templates in strict CI runs)
+// Infos only explain what was skipped on purpose, like schema documents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+// Field order defines the report order: by file, then position
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Diagnostic {
+    #[serde(rename = "fileName")]
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub col: u32,
+    pub kind: DiagnosticKind,
+    pub phase: Phase,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    // Parse errors name the language that failed to parse
+    pub fn label(&self) -> String {
+        match (self.kind, self.phase) {
+            (DiagnosticKind::ParseError, Phase::Extraction) => {
+                "TypeScript syntax error".to_string()
+            }
+            (DiagnosticKind::ParseError, Phase::GraphQL) => "GraphQL syntax error".to_string(),
+            (kind, _) => kind.to_string(),
+        }
+    }
+}
+
+// Turns diagnostics of the given kind into errors
+pub fn promote_to_errors(diagnostics: &mut [Diagnostic], kind: DiagnosticKind) {
+    for diagnostic in diagnostics.iter_mut().filter(|d| d.kind == kind) {
+        diagnostic.severity = Severity::Error;
+    }
+}
+
+// Syntax error with its position, turned into a `ParseError` diagnostic by the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxError {
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub col: u32,
+    pub phase: Phase,
+    pub message: String,
+}
+
+impl std::fmt::Display for SyntaxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Git root for relative paths in snapshots
+        let git_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let relative_path = self
+            .file_path
+            .strip_prefix(&git_root)
+            .unwrap_or(&self.file_path);
+        let language = match self.phase {
+            Phase::Extraction => "TypeScript",
+            Phase::GraphQL => "GraphQL",
+        };
+
+        write!(
+            f,
+            "{language} syntax error in {}:{}:{}: {}",
+            relative_path.display(),
+            self.line,
+            self.col,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+// Schema document found where operations were expected, turned into an info by the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedSchema {
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl std::fmt::Display for SkippedSchema {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Git root for relative paths in snapshots
+        let git_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let relative_path = self
+            .file_path
+            .strip_prefix(&git_root)
+            .unwrap_or(&self.file_path);
+
+        write!(
+            f,
+            "schema definitions in {}:{}:{} contain no operations to validate",
+            relative_path.display(),
+            self.line,
+            self.col
+        )
+    }
+}
+
+impl std::error::Error for SkippedSchema {}
+
+impl From<SkippedSchema> for Diagnostic {
+    fn from(schema: SkippedSchema) -> Self {
+        Diagnostic {
+            file_path: schema.file_path,
+            line: schema.line,
+            col: schema.col,
+            kind: DiagnosticKind::SkippedSchema,
+            phase: Phase::GraphQL,
+            severity: Severity::Info,
+            message: "schema definitions contain no operations to validate".to_string(),
+        }
+    }
+}
+
+impl From<SyntaxError> for Diagnostic {
+    fn from(error: SyntaxError) -> Self {
+        Diagnostic {
+            file_path: error.file_path,
+            line: error.line,
+            col: error.col,
+            kind: DiagnosticKind::ParseError,
+            phase: error.phase,
+            severity: Severity::Warning,
+            message: error.message,
+        }
+    }
+}
+
+// Syntax errors know their position, anything else (e.g. unreadable files) is reported for the whole file
+pub(crate) fn error_diagnostic(file: &Path, error: anyhow::Error, phase: Phase) -> Diagnostic {
+    let error = match error.downcast::<SkippedSchema>() {
+        Ok(schema) => return schema.into(),
+        Err(error) => error,
+    };
+    match error.downcast::<SyntaxError>() {
+        Ok(syntax_error) => syntax_error.into(),
+        Err(error) => Diagnostic {
+            file_path: file.to_path_buf(),
+            line: 1,
+            col: 1,
+            kind: DiagnosticKind::ParseError,
+            phase,
+            severity: Severity::Warning,
+            message: error.to_string(),
+        },
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Git root for relative paths in snapshots
+        let git_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let relative_path = self
+            .file_path
+            .strip_prefix(&git_root)
+            .unwrap_or(&self.file_path);
+
+        let icon = match self.severity {
+            Severity::Info => "ℹ️ ",
+            Severity::Warning => "⚠️ ",
+            Severity::Error => "❌",
+        };
+        write!(
+            f,
+            "{icon} {}: {}:{}:{} {}",
+            self.label(),
+            relative_path.display(),
+            self.line,
+            self.col,
+            self.message
+        )
+    }
+}
@@ -0,0 +1,329 @@
+//! Circular fragment reference detection
+//!
+//! Fragments spreading each other in a loop are invalid GraphQL and hide their content from
+//! protection checks. Each strongly connected component of the spread graph is one cycle.
+
+use rustc_hash::FxHashMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+use crate::parsers::graphql_parser::FragmentSpread;
+use crate::registry::GraphQLRegistry;
+
+// One link of a cycle: `fragment` spreads the next fragment of the chain at `line:col`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleStep {
+    pub fragment: String,
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FragmentCycle {
+    // Closed chain starting (and ending) at the alphabetically first fragment of the component
+    pub steps: Vec<CycleStep>,
+    // Fragments of the same component that aren't part of the shortest chain
+    pub other_fragments: Vec<String>,
+}
+
+impl FragmentCycle {
+    // `A -> B -> C -> A`
+    pub fn chain(&self) -> String {
+        let mut names: Vec<&str> = self
+            .steps
+            .iter()
+            .map(|step| step.fragment.as_str())
+            .collect();
+        if let Some(first) = names.first().copied() {
+            names.push(first);
+        }
+        names.join(" -> ")
+    }
+}
+
+// Spread graph of the registry - nodes are fragment names in alphabetical order
+struct SpreadGraph {
+    names: Vec<String>,
+    file_paths: Vec<PathBuf>,
+    // First spread of every referenced fragment, unknown fragments are left out
+    edges: Vec<Vec<(usize, FragmentSpread)>>,
+}
+
+impl SpreadGraph {
+    fn new(registry: &GraphQLRegistry) -> Self {
+        let mut names: Vec<String> = registry
+            .fragments
+            .iter()
+            .map(|entry| entry.key().clone())
+            .collect();
+        names.sort();
+        let index: FxHashMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.as_str(), i))
+            .collect();
+
+        let mut file_paths = Vec::with_capacity(names.len());
+        let mut edges = Vec::with_capacity(names.len());
+        for name in &names {
+            let fragment = registry
+                .fragment(name)
+                .expect("fragment names are taken from the registry");
+            let mut targets: Vec<(usize, FragmentSpread)> = Vec::new();
+            for spread in fragment.fragments() {
+                let Some(&target) = index.get(spread.name.as_str()) else {
+                    continue;
+                };
+                if !targets.iter().any(|(existing, _)| *existing == target) {
+                    targets.push((target, spread));
+                }
+            }
+            file_paths.push(fragment.file_path);
+            edges.push(targets);
+        }
+
+        Self {
+            names,
+            file_paths,
+            edges,
+        }
+    }
+
+    // Tarjan's algorithm - components come out in reverse topological order
+    // Iterative so long generated spread chains can't overflow the call stack
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        let node_count = self.names.len();
+        let mut next_index = 0;
+        let mut index: Vec<Option<usize>> = vec![None; node_count];
+        let mut lowlink = vec![0; node_count];
+        let mut on_stack = vec![false; node_count];
+        let mut stack = Vec::new();
+        let mut components = Vec::new();
+        // Nodes being visited with the position of their next spread to follow
+        let mut work: Vec<(usize, usize)> = Vec::new();
+
+        for root in 0..node_count {
+            if index[root].is_some() {
+                continue;
+            }
+            work.push((root, 0));
+
+            while let Some((node, edge)) = work.pop() {
+                if edge == 0 {
+                    index[node] = Some(next_index);
+                    lowlink[node] = next_index;
+                    next_index += 1;
+                    stack.push(node);
+                    on_stack[node] = true;
+                }
+
+                if let Some(&(target, _)) = self.edges[node].get(edge) {
+                    // Resume with the next spread once the target is done
+                    work.push((node, edge + 1));
+                    match index[target] {
+                        None => work.push((target, 0)),
+                        Some(target_index) if on_stack[target] => {
+                            lowlink[node] = lowlink[node].min(target_index);
+                        }
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                if Some(lowlink[node]) == index[node] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+
+                // The node that spread this one continues with the lowlink found below it
+                if let Some(&(parent, _)) = work.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+            }
+        }
+        components
+    }
+
+    // Shortest path from `start` back to itself that stays inside the component
+    fn shortest_cycle(&self, start: usize, component: &[usize]) -> Vec<usize> {
+        let mut previous: FxHashMap<usize, usize> = FxHashMap::default();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            for (target, _) in &self.edges[node] {
+                if *target == start {
+                    let mut path = vec![node];
+                    while let Some(&parent) = previous.get(path.last().unwrap()) {
+                        path.push(parent);
+                    }
+                    path.reverse();
+                    return path;
+                }
+                if component.contains(target) && !previous.contains_key(target) {
+                    previous.insert(*target, node);
+                    queue.push_back(*target);
+                }
+            }
+        }
+
+        vec![start]
+    }
+
+    fn cycle(&self, component: &[usize]) -> FragmentCycle {
+        let start = *component.iter().min().expect("components are never empty");
+        let path = self.shortest_cycle(start, component);
+
+        let steps = path
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| {
+                let next = path[(i + 1) % path.len()];
+                let (_, spread) = self.edges[node]
+                    .iter()
+                    .find(|(target, _)| *target == next)
+                    .expect("cycle follows existing spreads");
+                CycleStep {
+                    fragment: self.names[node].clone(),
+                    file_path: self.file_paths[node].clone(),
+                    line: spread.line,
+                    col: spread.col,
+                }
+            })
+            .collect();
+
+        let mut other_fragments: Vec<String> = component
+            .iter()
+            .filter(|node| !path.contains(node))
+            .map(|&node| self.names[node].clone())
+            .collect();
+        other_fragments.sort();
+
+        FragmentCycle {
+            steps,
+            other_fragments,
+        }
+    }
+}
+
+// Entry point: every group of fragments spreading each other, sorted by their first fragment
+pub fn find_fragment_cycles(registry: &GraphQLRegistry) -> Vec<FragmentCycle> {
+    let graph = SpreadGraph::new(registry);
+
+    let mut cycles: Vec<FragmentCycle> = graph
+        .strongly_connected_components()
+        .into_iter()
+        .filter(|component| match component.as_slice() {
+            // A single fragment is only a cycle if it spreads itself
+            [node] => graph.edges[*node].iter().any(|(target, _)| target == node),
+            _ => true,
+        })
+        .map(|component| graph.cycle(&component))
+        .collect();
+
+    cycles.sort_by(|a, b| a.steps[0].fragment.cmp(&b.steps[0].fragment));
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::graphql_parser::{parse_graphql_to_ast, GraphQLItem};
+    use crate::parsers::typescript_parser::GraphQLString;
+
+    fn registry_from_document(document: &str) -> GraphQLRegistry {
+        let registry = GraphQLRegistry::new();
+        let graphql_string = GraphQLString {
+            content: document.to_string(),
+            file_path: PathBuf::from("cycles.graphql"),
+            line_number: 1,
+            column_number: 1,
+            line_map: Vec::new(),
+        };
+        for item in parse_graphql_to_ast(&graphql_string).unwrap() {
+            if let GraphQLItem::Fragment(fragment) = item {
+                registry
+                    .fragments
+                    .entry(fragment.name.clone())
+                    .or_default()
+                    .push(fragment);
+            }
+        }
+        registry
+    }
+
+    #[test]
+    fn test_finds_every_cycle() {
+        let registry = registry_from_document(
+            "fragment A on User { ...B }
+fragment B on User { ...C ...Unknown }
+fragment C on User { ...A ...D }
+fragment D on User { id }
+fragment Self on User { ...Self }
+fragment X on User { ...Y }
+fragment Y on User { ...X }",
+        );
+
+        let chains: Vec<String> = find_fragment_cycles(&registry)
+            .iter()
+            .map(FragmentCycle::chain)
+            .collect();
+        assert_eq!(
+            chains,
+            vec!["A -> B -> C -> A", "Self -> Self", "X -> Y -> X"]
+        );
+    }
+
+    #[test]
+    fn test_reports_spread_locations_and_other_members() {
+        let registry = registry_from_document(
+            "fragment A on User {
+  ...B
+}
+fragment B on User {
+  ...A
+  ...C
+}
+fragment C on User {
+  ...B
+}",
+        );
+
+        let cycles = find_fragment_cycles(&registry);
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].chain(), "A -> B -> A");
+        assert_eq!(cycles[0].other_fragments, vec!["C".to_string()]);
+        assert_eq!(
+            cycles[0]
+                .steps
+                .iter()
+                .map(|step| (step.line, step.col))
+                .collect::<Vec<_>>(),
+            vec![(2, 6), (5, 6)]
+        );
+    }
+
+    #[test]
+    fn test_long_spread_chain() {
+        // Deep enough to overflow the call stack of a recursive traversal
+        let length = 20_000;
+        let mut document: String = (0..length)
+            .map(|i| format!("fragment F{i} on User {{ ...F{} }}\n", i + 1))
+            .collect();
+        document.push_str(&format!(
+            "fragment F{length} on User {{ ...F{} }}",
+            length - 1
+        ));
+
+        let cycles = find_fragment_cycles(&registry_from_document(&document));
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].steps.len(), 2);
+    }
+}
@@ -1,6 +1,16 @@
+pub mod baseline;
+pub mod code_frame;
+pub mod codes;
+pub mod config;
+pub mod diagnostics;
+pub mod fragment_cycles;
 pub mod parsers;
 pub mod registry;
 pub mod registry_to_graph;
+pub mod reporters;
+pub mod rules;
+#[cfg(test)]
+mod test_utils;
 pub mod tree_formatter;
 pub mod validate_registry;
 
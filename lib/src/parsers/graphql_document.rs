@@ -0,0 +1,181 @@
+//! Standalone `.graphql` / `.gql` documents
+//!
+//! Documents are used as-is so GraphQL positions are file positions. `#import` directives
+//! pull the referenced documents into the same registry, even outside the scanned pattern.
+//! Every document is parsed with the configured ignore policy on the way, so imported ones
+//! that can't be parsed are reported for the document they are in.
+
+use anyhow::Result;
+use rustc_hash::FxHashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{error_diagnostic, Diagnostic, DiagnosticKind, Phase, Severity};
+use crate::parsers::graphql_parser::parse_graphql_to_ast_with_policy;
+use crate::parsers::module_resolver::normalize_path;
+use crate::parsers::typescript_parser::{ExtractionOptions, GraphQLString};
+use crate::parsers::FileExtraction;
+
+pub fn is_graphql_document(file_path: &Path) -> bool {
+    matches!(
+        file_path.extension().and_then(|s| s.to_str()),
+        Some("graphql" | "gql")
+    )
+}
+
+// Reads a document plus everything it (transitively) `#import`s
+pub fn extract_graphql_from_document(
+    file_path: &Path,
+    options: &ExtractionOptions,
+) -> Result<FileExtraction> {
+    let content = fs::read_to_string(file_path)?;
+    let mut extraction = FileExtraction {
+        source: content.clone(),
+        ..FileExtraction::default()
+    };
+    let mut visited = FxHashSet::default();
+    visited.insert(file_path.to_path_buf());
+    collect_document(file_path, content, options, &mut visited, &mut extraction);
+    Ok(extraction)
+}
+
+fn collect_document(
+    file_path: &Path,
+    content: String,
+    options: &ExtractionOptions,
+    visited: &mut FxHashSet<PathBuf>,
+    extraction: &mut FileExtraction,
+) {
+    let imports: Vec<(u32, String)> = content
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index as u32 + 1, import_specifier(line)?.to_string())))
+        .collect();
+
+    let graphql_string = GraphQLString {
+        content,
+        file_path: file_path.to_path_buf(),
+        line_number: 1,
+        column_number: 1,
+        line_map: Vec::new(),
+    };
+    match parse_graphql_to_ast_with_policy(&graphql_string, options.ignore_policy) {
+        Ok(_) => extraction.graphql_strings.push(graphql_string),
+        Err(error) => {
+            extraction
+                .diagnostics
+                .push(error_diagnostic(file_path, error, Phase::GraphQL))
+        }
+    }
+
+    for (line, specifier) in imports {
+        let imported = file_path
+            .parent()
+            .map(|dir| normalize_path(&dir.join(&specifier)));
+        let imported_content = imported
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok());
+
+        match (imported, imported_content) {
+            (Some(imported), Some(imported_content)) => {
+                // Shared documents are commonly imported from several places (or each other)
+                if visited.insert(imported.clone()) {
+                    collect_document(&imported, imported_content, options, visited, extraction);
+                }
+            }
+            _ => extraction.diagnostics.push(Diagnostic {
+                file_path: file_path.to_path_buf(),
+                line,
+                col: 1,
+                kind: DiagnosticKind::UnresolvedImport,
+                phase: Phase::Extraction,
+                severity: Severity::Warning,
+                message: format!("`#import \"{specifier}\"` could not be resolved"),
+            }),
+        }
+    }
+}
+
+// `#import "./fragment.graphql"` (single or double quotes) -> `./fragment.graphql`
+fn import_specifier(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix("#import")?.trim();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    rest[1..].strip_suffix(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_path;
+
+    #[test]
+    fn test_follows_imports() {
+        let extraction = extract_graphql_from_document(
+            &fixture_path("valid/profile_document.graphql"),
+            &ExtractionOptions::default(),
+        )
+        .unwrap();
+
+        let files: Vec<_> = extraction
+            .graphql_strings
+            .iter()
+            .map(|graphql_string| graphql_string.file_path.clone())
+            .collect();
+        assert_eq!(
+            files,
+            vec![
+                fixture_path("valid/profile_document.graphql"),
+                fixture_path("shared/graphql/user_fields.graphql"),
+            ]
+        );
+        assert!(extraction.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn test_reports_unresolved_imports() {
+        let extraction = extract_graphql_from_document(
+            &fixture_path("edge_cases/missing_import.gql"),
+            &ExtractionOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(extraction.graphql_strings.len(), 1);
+        assert_eq!(extraction.diagnostics.len(), 1);
+        assert_eq!(
+            extraction.diagnostics[0].kind,
+            DiagnosticKind::UnresolvedImport
+        );
+        assert_eq!(extraction.diagnostics[0].line, 1);
+    }
+
+    #[test]
+    fn test_reports_unparsable_documents() {
+        let extraction = extract_graphql_from_document(
+            &fixture_path("edge_cases/schema.graphql"),
+            &ExtractionOptions::default(),
+        )
+        .unwrap();
+
+        // Reported once here, the registry never sees the document
+        assert!(extraction.graphql_strings.is_empty());
+        assert_eq!(extraction.diagnostics.len(), 1);
+        assert_eq!(
+            extraction.diagnostics[0].kind,
+            DiagnosticKind::SkippedSchema
+        );
+    }
+
+    #[test]
+    fn test_import_specifier() {
+        assert_eq!(
+            import_specifier(r#"#import "./fields.graphql""#),
+            Some("./fields.graphql")
+        );
+        assert_eq!(
+            import_specifier("  #import '../shared/user.gql'  "),
+            Some("../shared/user.gql")
+        );
+        assert_eq!(import_specifier("# import is just a comment"), None);
+        assert_eq!(import_specifier("query Foo { id }"), None);
+    }
+}
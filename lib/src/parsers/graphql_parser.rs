@@ -5,12 +5,15 @@
 
 use std::path::PathBuf;
 
+use crate::codes::ValidationErrorType;
+use crate::diagnostics::{Phase, SkippedSchema, SyntaxError};
 use crate::parsers::typescript_parser::GraphQLString;
 use anyhow::Result;
-use graphql_parser::parse_query;
+use graphql_parser::query::ParseError;
 use graphql_parser::query::{
     Definition, Document as QueryDocument, OperationDefinition, SelectionSet,
 };
+use graphql_parser::{parse_query, parse_schema};
 use serde::{Deserialize, Serialize};
 
 // Backward compatibility for modules expecting flat field lists
@@ -68,6 +71,9 @@ pub struct FieldSelection {
 pub struct FragmentSpread {
     pub name: String,
     pub directives: Vec<Directive>,
+    // Position of the spread fragment name in the source file
+    pub line: u32,
+    pub col: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -77,12 +83,36 @@ pub struct InlineFragment {
     pub selections: Vec<Selection>,
 }
 
+// GraphQL operation type - all of them can contain throwing directives
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl std::fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationKind::Query => write!(f, "query"),
+            OperationKind::Mutation => write!(f, "mutation"),
+            OperationKind::Subscription => write!(f, "subscription"),
+        }
+    }
+}
+
+// Any operation (query, mutation or subscription) - named after the most common kind
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct QueryOperation {
     pub name: String,
+    pub kind: OperationKind,
+    // Unnamed operation, `name` is only a placeholder like `AnonymousQuery`
+    pub anonymous: bool,
     pub selections: Vec<Selection>,
     pub directives: Vec<Directive>,
     pub file_path: PathBuf,
+    pub line: u32,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -92,13 +122,33 @@ pub struct FragmentDefinition {
     pub selections: Vec<Selection>,
     pub directives: Vec<Directive>,
     pub file_path: PathBuf,
+    pub line: u32,
+}
+
+// Which `# gql-safeguard-ignore` comments are honored, e.g. `ignoreComments = "codes"`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IgnorePolicy {
+    // Bare comments ignore every rule, comments listing codes only those
+    #[default]
+    Any,
+    // Only comments listing codes count, so every ignore names the rules it hides
+    Codes,
+    // Ignore comments are not honored at all
+    Off,
 }
 
 // Checks if a directive at a specific line should be ignored based on gql-safeguard-ignore comments
 // Only checks the line immediately before the directive - no complex parsing logic needed
-fn should_ignore_directive(graphql_content: &str, directive_line: usize) -> bool {
-    // Early exit if no ignore comments present
-    if !graphql_content.contains("# gql-safeguard-ignore") {
+// `# gql-safeguard-ignore` ignores every rule, `# gql-safeguard-ignore GQS001, GQS002` only those codes
+fn should_ignore_directive(
+    graphql_content: &str,
+    directive_line: usize,
+    code: &str,
+    policy: IgnorePolicy,
+) -> bool {
+    // Early exit if no ignore comments present or honored
+    if policy == IgnorePolicy::Off || !graphql_content.contains("# gql-safeguard-ignore") {
         return false;
     }
 
@@ -114,35 +164,80 @@ fn should_ignore_directive(graphql_content: &str, directive_line: usize) -> bool
     // Check if the line immediately before contains the ignore comment
     if directive_line_index > 0 {
         let previous_line = lines[directive_line_index - 1].trim();
-        if previous_line == "# gql-safeguard-ignore" {
-            return true;
+        if let Some(codes) = previous_line.strip_prefix("# gql-safeguard-ignore") {
+            if codes.is_empty() {
+                return policy == IgnorePolicy::Any;
+            }
+            // Codes must be separated from the comment, `# gql-safeguard-ignored` is no ignore comment
+            if codes.starts_with(char::is_whitespace) {
+                return codes
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .any(|listed| listed.eq_ignore_ascii_case(code));
+            }
         }
     }
 
     false
 }
 
+// graphql-parser only exposes errors as text: "Parse error at 3:3\nUnexpected ...\nExpected ..."
+fn syntax_error(error: &ParseError, graphql_string: &GraphQLString) -> SyntaxError {
+    let text = error.to_string();
+    let text = text.strip_prefix("query parse error: ").unwrap_or(&text);
+    let mut lines = text.lines();
+
+    let position = lines
+        .next()
+        .and_then(|line| line.strip_prefix("Parse error at "))
+        .and_then(|position| position.split_once(':'))
+        .and_then(|(line, col)| Some((line.parse::<u32>().ok()?, col.parse::<u32>().ok()?)));
+    let message = match position {
+        Some(_) => lines
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(", "),
+        None => text.trim().to_string(),
+    };
+
+    // Positions are relative to the extracted content - map them back to the source file
+    let (content_line, content_col) = position.unwrap_or((1, 1));
+    let (file_path, line, col) = graphql_string.original_position(content_line, content_col);
+    SyntaxError {
+        file_path: file_path.to_path_buf(),
+        line,
+        col,
+        phase: Phase::GraphQL,
+        message,
+    }
+}
+
 // Entry point: converts GraphQL strings to AST with safety-relevant directives
 pub fn parse_graphql_to_ast(graphql_string: &GraphQLString) -> Result<Vec<GraphQLItem>> {
+    parse_graphql_to_ast_with_policy(graphql_string, IgnorePolicy::default())
+}
+
+// Same as `parse_graphql_to_ast` with a configured policy for ignore comments
+pub fn parse_graphql_to_ast_with_policy(
+    graphql_string: &GraphQLString,
+    policy: IgnorePolicy,
+) -> Result<Vec<GraphQLItem>> {
     // Validate GraphQL syntax and build AST representation
-    let document: QueryDocument<String> = parse_query(&graphql_string.content).map_err(|e| {
-        // Normalize path to relative for consistent error messages
-        let git_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .parent()
-            .unwrap()
-            .to_path_buf();
-        let relative_path = graphql_string
-            .file_path
-            .strip_prefix(&git_root)
-            .unwrap_or(&graphql_string.file_path);
-
-        anyhow::anyhow!(
-            "GraphQL syntax error in {} at line {}: {:?}",
-            relative_path.display(),
-            graphql_string.line_number,
-            e
-        )
-    })?;
+    let document: QueryDocument<String> = match parse_query(&graphql_string.content) {
+        Ok(document) => document,
+        // Schema documents (e.g. a `schema.graphql` next to the queries) contain no operations,
+        // they are reported as skipped rather than as a syntax error
+        Err(_) if parse_schema::<String>(&graphql_string.content).is_ok() => {
+            let (file_path, line, col) = graphql_string.original_position(1, 1);
+            return Err(SkippedSchema {
+                file_path: file_path.to_path_buf(),
+                line,
+                col,
+            }
+            .into());
+        }
+        Err(error) => return Err(syntax_error(&error, graphql_string).into()),
+    };
 
     let mut items = Vec::new();
 
@@ -150,22 +245,11 @@ pub fn parse_graphql_to_ast(graphql_string: &GraphQLString) -> Result<Vec<GraphQ
     for definition in document.definitions {
         match definition {
             Definition::Operation(op) => {
-                if let Some(query) = convert_operation_to_query(
-                    op,
-                    &graphql_string.file_path,
-                    graphql_string.line_number,
-                    &graphql_string.content,
-                )? {
-                    items.push(GraphQLItem::Query(query));
-                }
+                let query = convert_operation_to_query(op, graphql_string, policy)?;
+                items.push(GraphQLItem::Query(query));
             }
             Definition::Fragment(frag) => {
-                let fragment = convert_fragment_definition(
-                    frag,
-                    &graphql_string.file_path,
-                    graphql_string.line_number,
-                    &graphql_string.content,
-                )?;
+                let fragment = convert_fragment_definition(frag, graphql_string, policy)?;
                 items.push(GraphQLItem::Fragment(fragment));
             }
         }
@@ -175,61 +259,90 @@ pub fn parse_graphql_to_ast(graphql_string: &GraphQLString) -> Result<Vec<GraphQ
 }
 
 // Converts parsed operations to internal format for validation
-// Focuses on queries since @throwOnFieldError is query-specific
+// Mutation responses and subscription events are checked like query results
 fn convert_operation_to_query(
     op: OperationDefinition<String>,
-    file_path: &std::path::Path,
-    line_number: u32,
-    graphql_content: &str,
-) -> Result<Option<QueryOperation>> {
-    match op {
-        OperationDefinition::Query(query) => {
-            // Anonymous queries need names for error reporting
-            let name = query.name.unwrap_or_else(|| "AnonymousQuery".to_string());
-
-            // Query-level directives affect all nested selections
-            let directives = extract_directives_from_directive_list(
-                &query.directives,
-                line_number,
-                graphql_content,
-            );
-
-            // Maintain nesting for proper directive inheritance validation
-            let selections =
-                convert_selection_set(&query.selection_set, line_number, graphql_content);
-
-            Ok(Some(QueryOperation {
-                name,
-                selections,
-                directives,
-                file_path: file_path.to_path_buf(),
-            }))
-        }
-        OperationDefinition::Mutation(_) | OperationDefinition::Subscription(_) => {
-            // @throwOnFieldError is primarily used in data-fetching queries
-            Ok(None)
-        }
-        OperationDefinition::SelectionSet(_) => {
-            // Rare pattern - focus on named operations for now
-            Ok(None)
-        }
-    }
+    graphql_string: &GraphQLString,
+    policy: IgnorePolicy,
+) -> Result<QueryOperation> {
+    let (kind, name, directives, selection_set, position) = match &op {
+        OperationDefinition::Query(query) => (
+            OperationKind::Query,
+            &query.name,
+            query.directives.as_slice(),
+            &query.selection_set,
+            query.position,
+        ),
+        OperationDefinition::Mutation(mutation) => (
+            OperationKind::Mutation,
+            &mutation.name,
+            mutation.directives.as_slice(),
+            &mutation.selection_set,
+            mutation.position,
+        ),
+        OperationDefinition::Subscription(subscription) => (
+            OperationKind::Subscription,
+            &subscription.name,
+            subscription.directives.as_slice(),
+            &subscription.selection_set,
+            subscription.position,
+        ),
+        // `{ viewer { name } }` shorthand is an anonymous query without directives
+        OperationDefinition::SelectionSet(selection_set) => (
+            OperationKind::Query,
+            &None,
+            [].as_slice(),
+            selection_set,
+            selection_set.span.0,
+        ),
+    };
+
+    // Anonymous operations need names for error reporting
+    let anonymous = name.is_none();
+    let name = name.clone().unwrap_or_else(|| match kind {
+        OperationKind::Query => "AnonymousQuery".to_string(),
+        OperationKind::Mutation => "AnonymousMutation".to_string(),
+        OperationKind::Subscription => "AnonymousSubscription".to_string(),
+    });
+
+    // Operation-level directives affect all nested selections
+    let directives = extract_directives_from_directive_list(directives, graphql_string, policy);
+
+    // Maintain nesting for proper directive inheritance validation
+    let selections = convert_selection_set(selection_set, graphql_string, policy);
+
+    // Interpolated documents may contain definitions written in other places
+    let (file_path, line, _) =
+        graphql_string.original_position(position.line as u32, position.column as u32);
+
+    Ok(QueryOperation {
+        name,
+        kind,
+        anonymous,
+        selections,
+        directives,
+        file_path: file_path.to_path_buf(),
+        line,
+    })
 }
 
 // Converts fragments for dependency resolution and validation
 // Fragments are key for @catch protection inheritance
 fn convert_fragment_definition(
     frag: graphql_parser::query::FragmentDefinition<String>,
-    file_path: &std::path::Path,
-    line_number: u32,
-    graphql_content: &str,
+    graphql_string: &GraphQLString,
+    policy: IgnorePolicy,
 ) -> Result<FragmentDefinition> {
     // Fragment-level directives protect all contained selections
     let directives =
-        extract_directives_from_directive_list(&frag.directives, line_number, graphql_content);
+        extract_directives_from_directive_list(&frag.directives, graphql_string, policy);
 
     // Maintain structure for nested directive validation
-    let selections = convert_selection_set(&frag.selection_set, line_number, graphql_content);
+    let selections = convert_selection_set(&frag.selection_set, graphql_string, policy);
+
+    // Interpolated fragments keep pointing at the file that defines them
+    let (file_path, line, _) =
+        graphql_string.original_position(frag.position.line as u32, frag.position.column as u32);
 
     Ok(FragmentDefinition {
         name: frag.name,
@@ -237,6 +350,7 @@ fn convert_fragment_definition(
         selections,
         directives,
         file_path: file_path.to_path_buf(),
+        line,
     })
 }
 
@@ -244,8 +358,8 @@ fn convert_fragment_definition(
 // Critical for validating @catch protection across nested selections
 fn convert_selection_set(
     selection_set: &SelectionSet<String>,
-    line_number: u32,
-    graphql_content: &str,
+    graphql_string: &GraphQLString,
+    policy: IgnorePolicy,
 ) -> Vec<Selection> {
     let mut selections = Vec::new();
 
@@ -255,13 +369,13 @@ fn convert_selection_set(
                 // Field directives can provide or require protection
                 let directives = extract_directives_from_directive_list(
                     &field.directives,
-                    line_number,
-                    graphql_content,
+                    graphql_string,
+                    policy,
                 );
 
                 // Fields may contain nested selections needing validation
                 let nested_selections =
-                    convert_selection_set(&field.selection_set, line_number, graphql_content);
+                    convert_selection_set(&field.selection_set, graphql_string, policy);
 
                 // Use alias if available, otherwise use field name
                 let effective_name = field.alias.as_ref().unwrap_or(&field.name).clone();
@@ -276,26 +390,31 @@ fn convert_selection_set(
                 // Spread directives can add protection before fragment expansion
                 let directives = extract_directives_from_directive_list(
                     &spread.directives,
-                    line_number,
-                    graphql_content,
+                    graphql_string,
+                    policy,
                 );
 
+                let (_, line, col) = graphql_string
+                    .original_position(spread.position.line as u32, spread.position.column as u32);
+
                 selections.push(Selection::FragmentSpread(FragmentSpread {
                     name: spread.fragment_name.clone(),
                     directives,
+                    line,
+                    col,
                 }));
             }
             graphql_parser::query::Selection::InlineFragment(inline) => {
                 // Inline fragments can provide @catch protection
                 let directives = extract_directives_from_directive_list(
                     &inline.directives,
-                    line_number,
-                    graphql_content,
+                    graphql_string,
+                    policy,
                 );
 
                 // Process inline fragment contents
                 let nested_selections =
-                    convert_selection_set(&inline.selection_set, line_number, graphql_content);
+                    convert_selection_set(&inline.selection_set, graphql_string, policy);
 
                 selections.push(Selection::InlineFragment(InlineFragment {
                     type_condition: inline.type_condition.as_ref().map(|tc| tc.to_string()),
@@ -309,6 +428,17 @@ fn convert_selection_set(
     selections
 }
 
+impl QueryOperation {
+    // Registry key - anonymous operations share their placeholder name, so their location tells them apart
+    pub fn id(&self) -> String {
+        if self.anonymous {
+            format!("{}@{}:{}", self.name, self.file_path.display(), self.line)
+        } else {
+            self.name.clone()
+        }
+    }
+}
+
 // Backward compatibility: convert hierarchical structure to flat lists
 impl QueryOperation {
     // Legacy API: flatten hierarchy to simple field list
@@ -390,39 +520,46 @@ fn extract_fragment_spreads_from_selections(selections: &[Selection]) -> Vec<Fra
 // Only processes @catch, @throwOnFieldError, and @required(action: THROW) - ignores irrelevant directives
 fn extract_directives_from_directive_list(
     directives: &[graphql_parser::query::Directive<String>],
-    base_line_number: u32,
-    graphql_content: &str,
+    graphql_string: &GraphQLString,
+    policy: IgnorePolicy,
 ) -> Vec<Directive> {
+    let graphql_content = graphql_string.content.as_str();
     directives
         .iter()
         .filter_map(|dir| {
-            // Calculate absolute line and column from GraphQL AST position and base line
-            // GraphQL AST line is 1-based, base_line_number is 1-based, so we add them and subtract 1
-            let directive_line = base_line_number + (dir.position.line as u32) - 1;
-            let directive_col = dir.position.column as u32;
+            // Map the GraphQL AST position (1-based) back to the line and column in the source file
+            let (_, directive_line, directive_col) = graphql_string
+                .original_position(dir.position.line as u32, dir.position.column as u32);
 
             // Skip directives that don't affect error handling safety
             let directive_type = match dir.name.as_str() {
                 "catch" => DirectiveType::Catch,
                 "throwOnFieldError" => {
                     // Check if this directive should be ignored (use GraphQL-relative line)
-                    if should_ignore_directive(graphql_content, dir.position.line) {
+                    if should_ignore_directive(
+                        graphql_content,
+                        dir.position.line,
+                        ValidationErrorType::UnprotectedThrowOnFieldError.code(),
+                        policy,
+                    ) {
                         return None; // Skip ignored @throwOnFieldError
                     }
                     DirectiveType::ThrowOnFieldError
                 }
-                "required" => {
-                    // Only process @required if it has action: THROW
-                    if has_throw_action(&dir.arguments) {
-                        // Check if this directive should be ignored (use GraphQL-relative line)
-                        if should_ignore_directive(graphql_content, dir.position.line) {
-                            return None; // Skip ignored @required(action: THROW)
-                        }
-                        DirectiveType::RequiredThrow
-                    } else {
-                        return None; // Ignore @required with other actions
+                // Only process @required if it has action: THROW
+                "required" if has_throw_action(&dir.arguments) => {
+                    // Check if this directive should be ignored (use GraphQL-relative line)
+                    if should_ignore_directive(
+                        graphql_content,
+                        dir.position.line,
+                        ValidationErrorType::UnprotectedRequiredThrow.code(),
+                        policy,
+                    ) {
+                        return None; // Skip ignored @required(action: THROW)
                     }
+                    DirectiveType::RequiredThrow
                 }
+                // Ignore @required with other actions and unrelated directives
                 _ => return None,
             };
 
@@ -446,7 +583,8 @@ fn has_throw_action(arguments: &[(String, graphql_parser::query::Value<String>)]
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::parsers::typescript_parser;
+    use crate::parsers::extract_graphql;
+    use crate::parsers::typescript_parser::ExtractionOptions;
     use std::fs;
     use std::path::PathBuf;
 
@@ -475,9 +613,9 @@ mod tests {
                                 directive.directive_type, emoji, directive.line, directive.col
                             ));
                         }
-                        result.push_str("]");
+                        result.push(']');
                     }
-                    result.push_str("\n");
+                    result.push('\n');
 
                     // Show nested structure with increased indentation
                     if !field.selections.is_empty() {
@@ -503,9 +641,9 @@ mod tests {
                                 directive.directive_type, emoji, directive.line, directive.col
                             ));
                         }
-                        result.push_str("]");
+                        result.push(']');
                     }
-                    result.push_str("\n");
+                    result.push('\n');
                 }
                 Selection::InlineFragment(inline) => {
                     result.push_str(&format!("{}- InlineFragment", indent));
@@ -529,9 +667,9 @@ mod tests {
                                 directive.directive_type, emoji, directive.line, directive.col
                             ));
                         }
-                        result.push_str("]");
+                        result.push(']');
                     }
-                    result.push_str("\n");
+                    result.push('\n');
 
                     // Show fragment content structure
                     if !inline.selections.is_empty() {
@@ -562,7 +700,7 @@ mod tests {
 
             match item {
                 GraphQLItem::Query(query) => {
-                    result.push_str(&format!("Type: Query\n"));
+                    result.push_str(&format!("Type: {:?}\n", query.kind));
                     result.push_str(&format!("Name: {}\n", query.name));
 
                     // Ensure all paths are portable in test output
@@ -590,7 +728,7 @@ mod tests {
                     format_selections(&mut result, &query.selections, 2);
                 }
                 GraphQLItem::Fragment(fragment) => {
-                    result.push_str(&format!("Type: Fragment\n"));
+                    result.push_str("Type: Fragment\n");
                     result.push_str(&format!("Name: {}\n", fragment.name));
 
                     // Consistent path formatting across all test output
@@ -621,7 +759,7 @@ mod tests {
                     format_selections(&mut result, &fragment.selections, 2);
                 }
             }
-            result.push_str("\n");
+            result.push('\n');
         }
 
         result
@@ -642,9 +780,7 @@ mod tests {
                 .filter_map(|entry| entry.ok())
                 .filter(|entry| {
                     let path = entry.path();
-                    path.is_file()
-                        && (path.extension() == Some(std::ffi::OsStr::new("ts"))
-                            || path.extension() == Some(std::ffi::OsStr::new("tsx")))
+                    path.is_file() && crate::parsers::is_supported_file(&path)
                 })
                 .collect();
 
@@ -655,10 +791,10 @@ mod tests {
                 let file_path = entry.path();
 
                 // Two-stage parsing: TS extraction then GraphQL parsing
-                match typescript_parser::extract_graphql_from_file(&file_path) {
-                    Ok(graphql_strings) => {
+                match extract_graphql(&file_path, &ExtractionOptions::default()) {
+                    Ok(extraction) => {
                         // Convert extracted strings to structured AST
-                        for graphql_string in graphql_strings {
+                        for graphql_string in extraction.graphql_strings {
                             match parse_graphql_to_ast(&graphql_string) {
                                 Ok(graphql_items) => {
                                     let result =
@@ -725,4 +861,38 @@ mod tests {
         let result = process_fixture_directory_to_ast("edge_cases");
         insta::assert_snapshot!(result);
     }
+
+    // Bare ignore comments only count with the default policy, none count when turned off
+    #[test]
+    fn test_ignore_policies() {
+        let graphql_string = GraphQLString {
+            content: "query IgnoreQuery {\n  # gql-safeguard-ignore\n  name @throwOnFieldError\n  # gql-safeguard-ignore GQS001\n  email @throwOnFieldError\n  avatar @throwOnFieldError\n}".to_string(),
+            file_path: PathBuf::from("ignore.graphql"),
+            line_number: 1,
+            column_number: 1,
+            line_map: Vec::new(),
+        };
+
+        let checked_fields = |policy| {
+            let items = parse_graphql_to_ast_with_policy(&graphql_string, policy).unwrap();
+            let [GraphQLItem::Query(query)] = items.as_slice() else {
+                panic!("expected a single query");
+            };
+            query
+                .selections
+                .iter()
+                .filter_map(|selection| match selection {
+                    Selection::Field(field) if !field.directives.is_empty() => {
+                        Some(field.name.as_str())
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        assert_eq!(checked_fields(IgnorePolicy::Any), "avatar");
+        assert_eq!(checked_fields(IgnorePolicy::Codes), "name, avatar");
+        assert_eq!(checked_fields(IgnorePolicy::Off), "name, email, avatar");
+    }
 }
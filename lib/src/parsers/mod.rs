@@ -2,5 +2,44 @@
 //!
 //! Separation enables robust extraction from complex TS/TSX without GraphQL syntax errors.
 
+pub mod graphql_document;
 pub mod graphql_parser;
+pub mod module_resolver;
+pub mod sfc_parser;
 pub mod typescript_parser;
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::diagnostics::Diagnostic;
+use graphql_document::{extract_graphql_from_document, is_graphql_document};
+use sfc_parser::{extract_graphql_from_component, is_component_file};
+use typescript_parser::{
+    extract_graphql_from_file_with_options, is_script_file, ExtractionOptions, GraphQLString,
+};
+
+// GraphQL documents of a single file plus everything that was skipped on the way
+#[derive(Debug, Clone, Default)]
+pub struct FileExtraction {
+    pub graphql_strings: Vec<GraphQLString>,
+    pub diagnostics: Vec<Diagnostic>,
+    // Content of the file, kept by the registry to show code frames
+    pub source: String,
+}
+
+// Files the extraction stages understand: JS/TS sources, components and GraphQL documents
+pub fn is_supported_file(file_path: &Path) -> bool {
+    is_script_file(file_path) || is_component_file(file_path) || is_graphql_document(file_path)
+}
+
+// Picks the extraction stage by file type: `.graphql`/`.gql` documents skip the TS parser,
+// components only pass their script blocks to it
+pub fn extract_graphql(file_path: &Path, options: &ExtractionOptions) -> Result<FileExtraction> {
+    if is_graphql_document(file_path) {
+        extract_graphql_from_document(file_path, options)
+    } else if is_component_file(file_path) {
+        extract_graphql_from_component(file_path, options)
+    } else {
+        extract_graphql_from_file_with_options(file_path, options)
+    }
+}
@@ -0,0 +1,295 @@
+//! Resolves import specifiers to files on disk
+//!
+//! Follows relative paths, index files and tsconfig `paths` aliases so interpolated
+//! fragments can be loaded from the module that defines them.
+
+use dashmap::DashMap;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+// Extensions tried in order when an import omits them (mirrors TypeScript's lookup)
+pub(crate) const SOURCE_EXTENSIONS: &[&str] =
+    &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+
+// Path aliases from a tsconfig.json (including the configs it extends)
+#[derive(Debug, Clone, Default)]
+struct PathAliases {
+    base_url: Option<PathBuf>,
+    // Directory `paths` targets are relative to when no baseUrl is set
+    paths_base: PathBuf,
+    paths: Vec<(String, Vec<String>)>,
+}
+
+#[derive(Deserialize, Default)]
+struct TsConfigFile {
+    extends: Option<String>,
+    #[serde(rename = "compilerOptions", default)]
+    compiler_options: TsCompilerOptions,
+}
+
+#[derive(Deserialize, Default)]
+struct TsCompilerOptions {
+    #[serde(rename = "baseUrl")]
+    base_url: Option<String>,
+    paths: Option<HashMap<String, Vec<String>>>,
+}
+
+// Files are processed in parallel and share the nearest tsconfig - parse each directory only once
+fn tsconfig_cache() -> &'static DashMap<PathBuf, Option<Arc<PathAliases>>> {
+    static CACHE: OnceLock<DashMap<PathBuf, Option<Arc<PathAliases>>>> = OnceLock::new();
+    CACHE.get_or_init(DashMap::new)
+}
+
+// Entry point: maps `import ... from '<specifier>'` in `from_file` to the imported file
+pub fn resolve_import(from_file: &Path, specifier: &str) -> Option<PathBuf> {
+    let from_dir = from_file.parent()?;
+
+    if specifier.starts_with("./") || specifier.starts_with("../") {
+        return resolve_path(&normalize_path(&from_dir.join(specifier)));
+    }
+
+    // Bare specifiers can only be resolved through tsconfig aliases
+    let aliases = nearest_path_aliases(from_dir)?;
+    for (pattern, targets) in &aliases.paths {
+        let Some(wildcard) = match_alias_pattern(pattern, specifier) else {
+            continue;
+        };
+        let base = aliases.base_url.as_deref().unwrap_or(&aliases.paths_base);
+        for target in targets {
+            let candidate = normalize_path(&base.join(target.replacen('*', wildcard, 1)));
+            if let Some(resolved) = resolve_path(&candidate) {
+                return Some(resolved);
+            }
+        }
+    }
+
+    let base_url = aliases.base_url.as_ref()?;
+    resolve_path(&normalize_path(&base_url.join(specifier)))
+}
+
+// Removes `.` and `..` segments so the same file always gets the same path in reports
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push(component);
+                }
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// Supports exact aliases and a single `*` wildcard like TypeScript does
+fn match_alias_pattern<'a>(pattern: &str, specifier: &'a str) -> Option<&'a str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix)),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+// Tries the path as-is, with source extensions appended and as a directory with an index file
+fn resolve_path(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_path_buf());
+    }
+
+    let base_str = base.to_string_lossy();
+
+    // ESM-style imports reference the compiled `.js` file of a `.ts` source
+    if let Some(stem) = base_str
+        .strip_suffix(".js")
+        .or_else(|| base_str.strip_suffix(".mjs"))
+        .or_else(|| base_str.strip_suffix(".cjs"))
+    {
+        for extension in ["ts", "tsx", "mts", "cts"] {
+            let candidate = PathBuf::from(format!("{stem}.{extension}"));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    for extension in SOURCE_EXTENSIONS {
+        let candidate = PathBuf::from(format!("{base_str}.{extension}"));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    if base.is_dir() {
+        for extension in SOURCE_EXTENSIONS {
+            let candidate = base.join(format!("index.{extension}"));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+// Walks up from `dir` to the closest tsconfig.json
+fn nearest_path_aliases(dir: &Path) -> Option<Arc<PathAliases>> {
+    if let Some(cached) = tsconfig_cache().get(dir) {
+        return cached.clone();
+    }
+
+    let tsconfig = dir.join("tsconfig.json");
+    let aliases = if tsconfig.is_file() {
+        load_path_aliases(&tsconfig, 0).map(Arc::new)
+    } else {
+        dir.parent().and_then(nearest_path_aliases)
+    };
+
+    tsconfig_cache().insert(dir.to_path_buf(), aliases.clone());
+    aliases
+}
+
+// Reads baseUrl/paths, letting the config override whatever it extends
+fn load_path_aliases(tsconfig_path: &Path, depth: usize) -> Option<PathAliases> {
+    // Guard against configs extending each other in a loop
+    if depth > 16 {
+        return None;
+    }
+
+    let content = fs::read_to_string(tsconfig_path).ok()?;
+    let config: TsConfigFile = serde_json::from_str(&strip_jsonc(&content)).ok()?;
+    let config_dir = tsconfig_path.parent()?;
+
+    // Only relative extends are followed - package configs rarely define project aliases
+    let mut aliases = config
+        .extends
+        .as_deref()
+        .filter(|extends| extends.starts_with('.'))
+        .and_then(|extends| {
+            let mut parent = config_dir.join(extends);
+            if parent.extension().is_none() {
+                parent.set_extension("json");
+            }
+            load_path_aliases(&parent, depth + 1)
+        })
+        .unwrap_or_else(|| PathAliases {
+            paths_base: config_dir.to_path_buf(),
+            ..Default::default()
+        });
+
+    if let Some(base_url) = config.compiler_options.base_url {
+        aliases.base_url = Some(config_dir.join(base_url));
+    }
+    if let Some(paths) = config.compiler_options.paths {
+        let mut paths: Vec<_> = paths.into_iter().collect();
+        // Longest prefix wins when several patterns match, like in TypeScript
+        paths.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        aliases.paths = paths;
+        aliases.paths_base = config_dir.to_path_buf();
+    }
+
+    Some(aliases)
+}
+
+// tsconfig.json allows comments and trailing commas which serde_json rejects
+fn strip_jsonc(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            result.push(c);
+            match c {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        result.push(escaped);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                result.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for skipped in chars.by_ref() {
+                    if skipped == '\n' {
+                        result.push('\n');
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = '\0';
+                for skipped in chars.by_ref() {
+                    if previous == '*' && skipped == '/' {
+                        break;
+                    }
+                    previous = skipped;
+                }
+            }
+            ',' => {
+                // Drop the comma if only whitespace separates it from a closing bracket
+                let next = chars.clone().find(|next| !next.is_whitespace());
+                if !matches!(next, Some('}') | Some(']')) {
+                    result.push(c);
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_path;
+
+    #[test]
+    fn test_resolves_relative_index_and_alias_imports() {
+        let from_file = fixture_path("valid/imported_fragments.ts");
+
+        assert_eq!(
+            resolve_import(&from_file, "../shared/fragments"),
+            Some(fixture_path("shared/fragments/index.ts"))
+        );
+        assert_eq!(
+            resolve_import(&from_file, "../shared/fragments/profile.js"),
+            Some(fixture_path("shared/fragments/profile.ts"))
+        );
+        assert_eq!(
+            resolve_import(&from_file, "@shared/order"),
+            Some(fixture_path("shared/fragments/order.ts"))
+        );
+        assert_eq!(resolve_import(&from_file, "react-relay"), None);
+    }
+
+    #[test]
+    fn test_strip_jsonc() {
+        let stripped = strip_jsonc(
+            r#"{
+  // line comment
+  "a": "http://not-a-comment", /* block */
+  "b": [1, 2,],
+}"#,
+        );
+        let value: serde_json::Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value["a"], "http://not-a-comment");
+        assert_eq!(value["b"], serde_json::json!([1, 2]));
+    }
+}
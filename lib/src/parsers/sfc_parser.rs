@@ -0,0 +1,276 @@
+//! GraphQL extraction from Vue, Svelte and Astro single-file components
+//!
+//! Script blocks (and Astro frontmatter) are handed to the SWC-based extraction. Each block
+//! is padded with the whitespace preceding it so reported lines and columns are those of
+//! the component file.
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+
+use crate::parsers::typescript_parser::{
+    extract_graphql_from_source, syntax_for_extension, ExtractionOptions,
+};
+use crate::parsers::FileExtraction;
+
+pub fn is_component_file(file_path: &Path) -> bool {
+    matches!(
+        file_path.extension().and_then(|s| s.to_str()),
+        Some("vue" | "svelte" | "astro")
+    )
+}
+
+// Code of a component that is run through the SWC parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScriptBlock {
+    // Byte range of the block content within the component
+    start: usize,
+    end: usize,
+    // Extension whose syntax the content uses (`ts`, `tsx`, `js`, ...)
+    extension: &'static str,
+}
+
+pub fn extract_graphql_from_component(
+    file_path: &Path,
+    options: &ExtractionOptions,
+) -> Result<FileExtraction> {
+    let source_code = fs::read_to_string(file_path)?;
+    let is_astro = file_path.extension().and_then(|s| s.to_str()) == Some("astro");
+
+    let mut extraction = FileExtraction::default();
+    for block in script_blocks(&source_code, is_astro) {
+        let block_extraction = extract_graphql_from_source(
+            file_path,
+            padded_block(&source_code, &block),
+            syntax_for_extension(block.extension),
+            options,
+        )?;
+        extraction
+            .graphql_strings
+            .extend(block_extraction.graphql_strings);
+        extraction.diagnostics.extend(block_extraction.diagnostics);
+    }
+    extraction.source = source_code;
+
+    Ok(extraction)
+}
+
+// Replaces everything before the block with blank lines/columns so positions stay the same
+fn padded_block(source_code: &str, block: &ScriptBlock) -> String {
+    let prefix = &source_code[..block.start];
+    let line_start = prefix.rfind('\n').map_or(0, |index| index + 1);
+
+    let mut padded = "\n".repeat(prefix.matches('\n').count());
+    padded.push_str(&" ".repeat(prefix[line_start..].chars().count()));
+    padded.push_str(&source_code[block.start..block.end]);
+    padded
+}
+
+// Astro frontmatter plus every inline `<script>` element containing JS or TS
+fn script_blocks(source_code: &str, is_astro: bool) -> Vec<ScriptBlock> {
+    let mut blocks = Vec::new();
+    let mut position = 0;
+
+    if is_astro {
+        if let Some(frontmatter) = astro_frontmatter(source_code) {
+            position = frontmatter.end;
+            blocks.push(frontmatter);
+        }
+    }
+
+    while let Some(offset) = source_code[position..].find('<') {
+        let tag_start = position + offset;
+        let rest = &source_code[tag_start..];
+
+        // Markup that is commented out never runs
+        if rest.starts_with("<!--") {
+            position = rest
+                .find("-->")
+                .map_or(source_code.len(), |end| tag_start + end + 3);
+            continue;
+        }
+
+        if !is_script_open_tag(rest) {
+            position = tag_start + 1;
+            continue;
+        }
+
+        let Some(tag_end) = rest.find('>').map(|end| tag_start + end + 1) else {
+            break;
+        };
+        let attributes = &source_code[tag_start + "<script".len()..tag_end - 1];
+
+        // `<script src="..." />` has no inline content (and no closing tag)
+        if attributes.trim_end().ends_with('/') {
+            position = tag_end;
+            continue;
+        }
+
+        let Some(close) = source_code[tag_end..].find("</script") else {
+            break;
+        };
+        let content_end = tag_end + close;
+
+        // Astro scripts are TypeScript, Vue and Svelte default to JavaScript
+        let default_extension = if is_astro { "ts" } else { "js" };
+        if let Some(extension) = script_extension(attributes, default_extension) {
+            blocks.push(ScriptBlock {
+                start: tag_end,
+                end: content_end,
+                extension,
+            });
+        }
+
+        position = content_end;
+    }
+
+    blocks
+}
+
+// `<script>` and `<script ...>` but not `<scripts>` or custom elements like `<script-loader>`
+fn is_script_open_tag(rest: &str) -> bool {
+    rest.get(..7)
+        .is_some_and(|tag| tag.eq_ignore_ascii_case("<script"))
+        && rest[7..]
+            .chars()
+            .next()
+            .is_some_and(|c| c == '>' || c == '/' || c.is_whitespace())
+}
+
+// Syntax of a script element, None for elements that don't contain code (JSON, templates, ...)
+fn script_extension(attributes: &str, default_extension: &'static str) -> Option<&'static str> {
+    if let Some(script_type) = attribute_value(attributes, "type") {
+        if !matches!(
+            script_type,
+            "module" | "text/javascript" | "application/javascript" | "text/typescript"
+        ) {
+            return None;
+        }
+    }
+
+    Some(match attribute_value(attributes, "lang") {
+        Some("ts" | "typescript") => "ts",
+        Some("tsx") => "tsx",
+        Some("js" | "javascript" | "jsx") => "js",
+        Some(_) => return None,
+        None => default_extension,
+    })
+}
+
+// Value of a quoted (or bare) attribute in the opening tag
+fn attribute_value<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let mut rest = attributes;
+    while let Some(index) = rest.find(name) {
+        let preceded_by_space = rest[..index]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        let after = rest[index + name.len()..].trim_start();
+        rest = &rest[index + name.len()..];
+
+        let Some(value) = after.strip_prefix('=').filter(|_| preceded_by_space) else {
+            continue;
+        };
+        let value = value.trim_start();
+        return match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next(),
+            _ => value.split(|c: char| c.is_whitespace() || c == '>').next(),
+        };
+    }
+    None
+}
+
+// Astro components start with TypeScript fenced by `---` lines
+fn astro_frontmatter(source_code: &str) -> Option<ScriptBlock> {
+    let leading_whitespace = source_code.len() - source_code.trim_start().len();
+    let after_fence = source_code[leading_whitespace..].strip_prefix("---")?;
+    let start = source_code.len() - after_fence.len();
+
+    let mut line_start = start;
+    for line in source_code[start..].split_inclusive('\n') {
+        if line_start > start && line.trim_end() == "---" {
+            return Some(ScriptBlock {
+                start,
+                end: line_start,
+                extension: "ts",
+            });
+        }
+        line_start += line.len();
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::fixture_path;
+
+    fn block_contents<'a>(source_code: &'a str, blocks: &[ScriptBlock]) -> Vec<(&'a str, &'a str)> {
+        blocks
+            .iter()
+            .map(|block| (block.extension, &source_code[block.start..block.end]))
+            .collect()
+    }
+
+    #[test]
+    fn test_script_blocks() {
+        let vue = r#"<template><div /></template>
+<!-- <script>commented()</script> -->
+<script>export default {}</script>
+<script setup lang="ts">const a = 1</script>
+<script type="application/ld+json">{"a": 1}</script>
+<script src="./external.js" />
+<script lang="tsx">const b = <b /></script>"#;
+        assert_eq!(
+            block_contents(vue, &script_blocks(vue, false)),
+            vec![
+                ("js", "export default {}"),
+                ("ts", "const a = 1"),
+                ("tsx", "const b = <b />")
+            ]
+        );
+
+        let astro = "---\nconst a = 1;\n---\n<h1>Hi</h1>\n<script>const b = 2</script>\n";
+        assert_eq!(
+            block_contents(astro, &script_blocks(astro, true)),
+            vec![("ts", "\nconst a = 1;\n"), ("ts", "const b = 2")]
+        );
+    }
+
+    #[test]
+    fn test_padded_block_keeps_positions() {
+        let source_code = "<template />\n  <script>const a = 1</script>";
+        let blocks = script_blocks(source_code, false);
+        assert_eq!(
+            padded_block(source_code, &blocks[0]),
+            "\n          const a = 1"
+        );
+    }
+
+    #[test]
+    fn test_extract_graphql_from_components() {
+        let mut result = String::new();
+        for relative in [
+            "valid/MarketingHero.astro",
+            "valid/UserCard.vue",
+            "invalid/ProfileBadge.svelte",
+        ] {
+            let extraction =
+                extract_graphql_from_component(&fixture_path(relative), &Default::default())
+                    .unwrap();
+            for graphql_string in &extraction.graphql_strings {
+                let first_line = graphql_string
+                    .content
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .unwrap_or_default();
+                result.push_str(&format!(
+                    "{relative}:{} {}\n",
+                    graphql_string.line_number,
+                    first_line.trim()
+                ));
+            }
+        }
+        insta::assert_snapshot!(result);
+    }
+}
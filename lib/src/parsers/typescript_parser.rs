@@ -3,13 +3,23 @@
 //! Avoids regex false positives from comments, strings, and complex TypeScript syntax.
 
 use anyhow::Result;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
-use swc_core::common::{BytePos, FileName, SourceMap};
+use swc_core::common::comments::{CommentKind, Comments, SingleThreadedComments};
+use swc_core::common::errors::SourceMapper;
+use swc_core::common::{BytePos, FileName, SourceMap, Span, Spanned};
+
+use crate::diagnostics::{Diagnostic, DiagnosticKind, Phase, Severity, SyntaxError};
+use crate::parsers::graphql_parser::IgnorePolicy;
+use crate::parsers::module_resolver::{resolve_import, SOURCE_EXTENSIONS};
+use crate::parsers::FileExtraction;
 use swc_core::ecma::{
     ast::*,
-    parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax},
+    parser::{lexer::Lexer, EsSyntax, Parser, StringInput, Syntax, TsSyntax},
     visit::{Visit, VisitWith},
 };
 
@@ -18,104 +28,984 @@ pub struct GraphQLString {
     pub content: String,
     pub file_path: std::path::PathBuf,
     pub line_number: u32,
+    // 1-based column the content starts at on `line_number` (after the backtick or quote)
+    pub column_number: u32,
+    // Origins of content stitched together from interpolated constants (empty for plain templates)
+    pub line_map: Vec<LineMapping>,
+}
+
+// Marks where a stitched piece of content starts and where it was originally written
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineMapping {
+    pub content_line: u32,
+    // Pieces spliced in after other content start in the middle of a line
+    pub content_col: u32,
+    pub file_path: PathBuf,
+    pub line_number: u32,
+    pub column_number: u32,
+}
+
+impl GraphQLString {
+    // Maps a 1-based line and column of `content` back to the file, line and column they were written at
+    // Only the first line of a piece is shifted, later lines are copied from the source as-is
+    pub fn original_position(&self, content_line: u32, content_col: u32) -> (&Path, u32, u32) {
+        match self.line_map.iter().rev().find(|mapping| {
+            (mapping.content_line, mapping.content_col) <= (content_line, content_col)
+        }) {
+            Some(mapping) if mapping.content_line == content_line => (
+                &mapping.file_path,
+                mapping.line_number,
+                mapping.column_number + content_col - mapping.content_col,
+            ),
+            Some(mapping) => (
+                &mapping.file_path,
+                mapping.line_number + content_line - mapping.content_line,
+                content_col,
+            ),
+            None if content_line == 1 => (
+                &self.file_path,
+                self.line_number,
+                self.column_number + content_col - 1,
+            ),
+            None => (
+                &self.file_path,
+                self.line_number + content_line - 1,
+                content_col,
+            ),
+        }
+    }
+}
+
+// Controls which expressions are treated as GraphQL documents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractionOptions {
+    // Tag and function names like `gql`, `graphql` or `Relay.QL`
+    // Member tags of a configured name (`gql.experimental`) are recognised as well
+    pub tag_names: Vec<String>,
+    // Import-aware mode: only accept tags imported from these modules (or their subpaths)
+    // None accepts every tag with a matching name
+    pub tag_modules: Option<Vec<String>>,
+    // Which `# gql-safeguard-ignore` comments are honored in the extracted documents
+    pub ignore_policy: IgnorePolicy,
+}
+
+// Modules exporting GraphQL tags in the import-aware mode unless configured otherwise
+pub const DEFAULT_TAG_MODULES: &[&str] = &[
+    "react-relay",
+    "relay-runtime",
+    "@apollo/client",
+    "graphql-tag",
+];
+
+impl Default for ExtractionOptions {
+    fn default() -> Self {
+        Self {
+            tag_names: vec!["gql".to_string(), "graphql".to_string()],
+            tag_modules: None,
+            ignore_policy: IgnorePolicy::default(),
+        }
+    }
+}
+
+impl ExtractionOptions {
+    // Matches tagged template tags and call-style callees against the configured names
+    fn is_graphql_tag(&self, tag: &Expr) -> bool {
+        let Some(path) = expression_path(tag) else {
+            return false;
+        };
+        self.tag_names.iter().any(|name| {
+            path == *name
+                || path
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    // Cheap text check to skip parsing files that can't contain any configured tag or magic comment
+    fn may_contain_graphql(&self, source_code: &str) -> bool {
+        // The magic comment is matched case-insensitively, so is its pre-filter
+        source_code
+            .as_bytes()
+            .windows("graphql".len())
+            .any(|window| window.eq_ignore_ascii_case(b"graphql"))
+            || self.tag_names.iter().any(|name| {
+                let root = name.split('.').next().unwrap_or(name);
+                source_code.contains(root)
+            })
+    }
+
+    fn is_tag_module(&self, source: &str) -> bool {
+        self.tag_modules.as_ref().is_none_or(|modules| {
+            modules.iter().any(|module| {
+                source == module
+                    || source
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        })
+    }
+
+    // Returns the tag and GraphQL template of gql`...`, graphql(`...`) or graphql("...")
+    fn graphql_template<'a>(&self, expr: &'a Expr) -> Option<(&'a Expr, Cow<'a, Tpl>)> {
+        match expr {
+            Expr::TaggedTpl(tagged) if self.is_graphql_tag(&tagged.tag) => {
+                Some((&tagged.tag, Cow::Borrowed(&tagged.tpl)))
+            }
+            Expr::Call(call) => {
+                let Callee::Expr(callee) = &call.callee else {
+                    return None;
+                };
+                if !self.is_graphql_tag(callee) {
+                    return None;
+                }
+                let tpl = match unwrap_type_assertions(&call.args.first()?.expr) {
+                    Expr::Tpl(tpl) => Cow::Borrowed(tpl),
+                    Expr::Lit(Lit::Str(str)) => Cow::Owned(string_template(str)),
+                    _ => return None,
+                };
+                Some((callee, tpl))
+            }
+            _ => None,
+        }
+    }
+}
+
+// String documents behave like a template without interpolations
+fn string_template(str: &Str) -> Tpl {
+    Tpl {
+        span: str.span,
+        exprs: Vec::new(),
+        quasis: vec![TplElement {
+            // The content starts after the opening quote
+            span: Span::new(str.span.lo + BytePos(1), str.span.hi),
+            tail: true,
+            cooked: None,
+            raw: str.value.clone(),
+        }],
+    }
+}
+
+// Identifier a tag starts with (`Relay` for `Relay.QL`)
+fn root_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Member(member) => root_ident(&member.obj),
+        _ => None,
+    }
+}
+
+// Dotted name of identifiers and member chains (`Relay.QL`), None for anything else
+fn expression_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => {
+            let MemberProp::Ident(prop) = &member.prop else {
+                return None;
+            };
+            Some(format!("{}.{}", expression_path(&member.obj)?, prop.sym))
+        }
+        _ => None,
+    }
 }
 
 // Finds GraphQL in TS/TSX files while avoiding dynamic content that can't be validated
 pub fn extract_graphql_from_file(file_path: &Path) -> Result<Vec<GraphQLString>> {
+    extract_graphql_from_file_with_options(file_path, &ExtractionOptions::default())
+        .map(|extraction| extraction.graphql_strings)
+}
+
+// Same as `extract_graphql_from_file` with custom tag names and import-aware tag detection
+pub fn extract_graphql_from_file_with_options(
+    file_path: &Path,
+    options: &ExtractionOptions,
+) -> Result<FileExtraction> {
     let source_code = fs::read_to_string(file_path)?;
+    let mut extraction = extract_graphql_from_source(
+        file_path,
+        source_code.clone(),
+        syntax_for(file_path),
+        options,
+    )?;
+    extraction.source = source_code;
+    Ok(extraction)
+}
 
+// Extraction from code that isn't the plain content of `file_path` (e.g. component script blocks)
+pub(crate) fn extract_graphql_from_source(
+    file_path: &Path,
+    source_code: String,
+    syntax: Syntax,
+    options: &ExtractionOptions,
+) -> Result<FileExtraction> {
     // Performance optimization: skip AST parsing for files without GraphQL
-    if !source_code.contains("gql") && !source_code.contains("graphql") {
-        return Ok(Vec::new());
+    if !options.may_contain_graphql(&source_code) {
+        return Ok(FileExtraction::default());
     }
 
-    let syntax = if file_path.extension().and_then(|s| s.to_str()) == Some("tsx") {
-        Syntax::Typescript(TsSyntax {
+    let (module, source_map, comments) = parse_module(file_path, source_code, syntax)?;
+
+    // Interpolated fragments reference constants that may be declared after their use
+    let scope = Rc::new(ModuleScope::collect(
+        file_path.to_path_buf(),
+        source_map,
+        comments,
+        &module,
+        options,
+    ));
+
+    let mut visitor = GraphQLVisitor::new(scope, options);
+    module.visit_with(&mut visitor);
+
+    Ok(FileExtraction {
+        graphql_strings: visitor.graphql_strings,
+        diagnostics: visitor.diagnostics,
+        source: String::new(),
+    })
+}
+
+// JS/TS sources the SWC parser handles directly
+pub fn is_script_file(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|extension| SOURCE_EXTENSIONS.contains(&extension))
+}
+
+fn syntax_for(file_path: &Path) -> Syntax {
+    syntax_for_extension(
+        file_path
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("ts"),
+    )
+}
+
+// Chooses the SWC syntax by extension - JavaScript files commonly contain JSX without a `.jsx` extension
+pub(crate) fn syntax_for_extension(extension: &str) -> Syntax {
+    match extension {
+        "js" | "jsx" | "mjs" | "cjs" => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        "tsx" => Syntax::Typescript(TsSyntax {
             tsx: true,
             ..Default::default()
-        })
-    } else {
-        Syntax::Typescript(TsSyntax {
+        }),
+        // `.ts`, `.mts` and `.cts` don't allow JSX since `<T>value` is a type assertion there
+        _ => Syntax::Typescript(TsSyntax {
             tsx: false,
             ..Default::default()
-        })
-    };
+        }),
+    }
+}
 
+// Parses a JS/TS module and keeps its source map for line lookups and comments for magic comments
+fn parse_module(
+    file_path: &Path,
+    source_code: String,
+    syntax: Syntax,
+) -> Result<(Module, Rc<SourceMap>, SingleThreadedComments)> {
     let source_map = Rc::new(SourceMap::default());
-    source_map.new_source_file(
-        FileName::Real(file_path.to_path_buf()).into(),
-        source_code.clone(),
-    );
+    let source_file =
+        source_map.new_source_file(FileName::Real(file_path.to_path_buf()).into(), source_code);
+    let comments = SingleThreadedComments::default();
 
     let lexer = Lexer::new(
         syntax,
         Default::default(),
-        StringInput::new(&source_code, BytePos(0), BytePos(source_code.len() as u32)),
-        None,
+        StringInput::from(&*source_file),
+        Some(&comments),
     );
 
     let mut parser = Parser::new_from(lexer);
-    let module = parser.parse_module().map_err(|e| {
-        anyhow::anyhow!("TypeScript parse error in {}: {:?}", file_path.display(), e)
+    let module = parser.parse_module().map_err(|error| {
+        let location = source_map.lookup_char_pos(error.span().lo());
+        SyntaxError {
+            file_path: file_path.to_path_buf(),
+            line: location.line as u32,
+            col: location.col_display as u32 + 1,
+            phase: Phase::Extraction,
+            message: error.kind().msg().to_string(),
+        }
     })?;
 
-    let mut visitor = GraphQLVisitor::new(file_path.to_path_buf(), source_map);
-    module.visit_with(&mut visitor);
+    Ok((module, source_map, comments))
+}
 
-    Ok(visitor.graphql_strings)
+// `gql`...` as DocumentNode` and friends still hold the template at runtime
+fn unwrap_type_assertions(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::TsAs(ts_as) => unwrap_type_assertions(&ts_as.expr),
+        Expr::TsSatisfies(satisfies) => unwrap_type_assertions(&satisfies.expr),
+        Expr::TsConstAssertion(assertion) => unwrap_type_assertions(&assertion.expr),
+        Expr::Paren(paren) => unwrap_type_assertions(&paren.expr),
+        _ => expr,
+    }
 }
 
-struct GraphQLVisitor {
-    file_path: std::path::PathBuf,
-    graphql_strings: Vec<GraphQLString>,
+// Import of a single binding: `import { name } from 'source'` (`default` and `*` for the other forms)
+#[derive(Debug, Clone)]
+struct ImportBinding {
+    source: String,
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+enum ExportBinding {
+    // `export { local as exported }`
+    Local(String),
+    // `export { name as exported } from 'source'`
+    ReExport(ImportBinding),
+}
+
+// Module bindings needed to resolve identifiers interpolated into gql templates
+struct ModuleScope {
+    file_path: PathBuf,
     source_map: Rc<SourceMap>,
+    comments: SingleThreadedComments,
+    // `const X = gql`...`` (or graphql(`...`), /* GraphQL */ `...`) bindings, `export default gql`...`` is stored as `default`
+    templates: FxHashMap<String, Tpl>,
+    // Local name -> where it was imported from
+    imports: FxHashMap<String, ImportBinding>,
+    // Exported name -> binding, for exports that don't share the local name
+    exports: FxHashMap<String, ExportBinding>,
+    // Sources of `export * from '...'`
+    star_exports: Vec<String>,
 }
 
-impl GraphQLVisitor {
+impl ModuleScope {
+    fn collect(
+        file_path: PathBuf,
+        source_map: Rc<SourceMap>,
+        comments: SingleThreadedComments,
+        module: &Module,
+        options: &ExtractionOptions,
+    ) -> Self {
+        let mut collector = ScopeCollector {
+            options,
+            scope: Self {
+                file_path,
+                source_map,
+                comments,
+                templates: FxHashMap::default(),
+                imports: FxHashMap::default(),
+                exports: FxHashMap::default(),
+                star_exports: Vec::new(),
+            },
+        };
+        // Imports come first so tags of constants can be checked against them in any order
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item {
+                collector.scope.collect_import(import);
+            }
+        }
+        module.visit_with(&mut collector);
+        collector.scope
+    }
+
+    fn collect_import(&mut self, import: &ImportDecl) {
+        let source = import.src.value.to_string();
+        for specifier in &import.specifiers {
+            let (local, name) = match specifier {
+                ImportSpecifier::Named(named) => (
+                    named.local.sym.to_string(),
+                    named
+                        .imported
+                        .as_ref()
+                        .map(module_export_name)
+                        .unwrap_or_else(|| named.local.sym.to_string()),
+                ),
+                ImportSpecifier::Default(default) => {
+                    (default.local.sym.to_string(), "default".to_string())
+                }
+                ImportSpecifier::Namespace(namespace) => {
+                    (namespace.local.sym.to_string(), "*".to_string())
+                }
+            };
+            self.imports.insert(
+                local,
+                ImportBinding {
+                    source: source.clone(),
+                    name,
+                },
+            );
+        }
+    }
+
+    // Whether the tag's root identifier is imported from a configured module (always true outside import-aware mode)
+    fn imports_tag(&self, tag: &Expr, options: &ExtractionOptions) -> bool {
+        options.tag_modules.is_none()
+            || root_ident(tag)
+                .and_then(|ident| self.imports.get(ident.sym.as_ref()))
+                .is_some_and(|import| options.is_tag_module(&import.source))
+    }
+
+    // Untagged template or string literal marked as GraphQL by a leading `/* GraphQL */` comment
+    fn magic_comment_template<'a>(&self, expr: &'a Expr) -> Option<Cow<'a, Tpl>> {
+        let (span, tpl) = match expr {
+            Expr::Tpl(tpl) => (tpl.span, Cow::Borrowed(tpl)),
+            Expr::Lit(Lit::Str(str)) => (str.span, Cow::Owned(string_template(str))),
+            _ => return None,
+        };
+        let comments = self.comments.get_leading(span.lo())?;
+        comments
+            .iter()
+            .any(|comment| {
+                comment.kind == CommentKind::Block
+                    && comment.text.trim().eq_ignore_ascii_case("graphql")
+            })
+            .then_some(tpl)
+    }
+
+    fn line_of(&self, byte_pos: BytePos) -> u32 {
+        self.source_map.lookup_char_pos(byte_pos).line as u32
+    }
+
+    fn col_of(&self, byte_pos: BytePos) -> u32 {
+        self.source_map.lookup_char_pos(byte_pos).col_display as u32 + 1
+    }
+}
+
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    }
+}
+
+// Collects bindings for the scope in a single pass over the module
+struct ScopeCollector<'a> {
+    options: &'a ExtractionOptions,
+    scope: ModuleScope,
+}
+
+impl ScopeCollector<'_> {
+    // GraphQL template held by a constant, tags have to pass the import-aware check
+    fn constant_template(&self, expr: &Expr) -> Option<Tpl> {
+        match self.options.graphql_template(expr) {
+            Some((tag, tpl)) => self
+                .scope
+                .imports_tag(tag, self.options)
+                .then(|| tpl.into_owned()),
+            None => self.scope.magic_comment_template(expr).map(Cow::into_owned),
+        }
+    }
+}
+
+impl Visit for ScopeCollector<'_> {
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        // Only const bindings are guaranteed to still hold the template when it is interpolated
+        if decl.kind == VarDeclKind::Const {
+            for declarator in &decl.decls {
+                if let (Pat::Ident(binding), Some(init)) = (&declarator.name, &declarator.init) {
+                    if let Some(tpl) = self.constant_template(unwrap_type_assertions(init)) {
+                        self.scope.templates.insert(binding.id.sym.to_string(), tpl);
+                    }
+                }
+            }
+        }
+
+        decl.visit_children_with(self);
+    }
+
+    fn visit_named_export(&mut self, export: &NamedExport) {
+        for specifier in &export.specifiers {
+            let (orig, exported) = match specifier {
+                ExportSpecifier::Named(named) => {
+                    let orig = module_export_name(&named.orig);
+                    let exported = named
+                        .exported
+                        .as_ref()
+                        .map(module_export_name)
+                        .unwrap_or_else(|| orig.clone());
+                    (orig, exported)
+                }
+                ExportSpecifier::Namespace(namespace) => {
+                    ("*".to_string(), module_export_name(&namespace.name))
+                }
+                ExportSpecifier::Default(_) => continue,
+            };
+            let binding = match &export.src {
+                Some(src) => ExportBinding::ReExport(ImportBinding {
+                    source: src.value.to_string(),
+                    name: orig,
+                }),
+                None => ExportBinding::Local(orig),
+            };
+            self.scope.exports.insert(exported, binding);
+        }
+    }
+
+    fn visit_export_all(&mut self, export: &ExportAll) {
+        self.scope.star_exports.push(export.src.value.to_string());
+    }
+
+    fn visit_export_default_expr(&mut self, export: &ExportDefaultExpr) {
+        let expr = unwrap_type_assertions(&export.expr);
+        if let Some(tpl) = self.constant_template(expr) {
+            self.scope.templates.insert("default".to_string(), tpl);
+        } else if let Expr::Ident(ident) = expr {
+            self.scope.exports.insert(
+                "default".to_string(),
+                ExportBinding::Local(ident.sym.to_string()),
+            );
+        }
+
+        export.visit_children_with(self);
+    }
+}
+
+// Template content with all interpolations replaced by the GraphQL they reference
+struct ResolvedTemplate {
+    content: String,
+    line_map: Vec<LineMapping>,
+}
+
+// Re-export chains longer than this are treated as unresolvable (and cut `export *` loops)
+const MAX_EXPORT_DEPTH: usize = 32;
+
+// Resolves interpolated identifiers across modules, parsing each imported module at most once
+struct TemplateResolver<'a> {
+    options: &'a ExtractionOptions,
+    modules: RefCell<FxHashMap<PathBuf, Option<Rc<ModuleScope>>>>,
+}
+
+impl TemplateResolver<'_> {
+    // Splices interpolated gql constants into the template while tracking where each line came from
+    // Fails with the interpolation that can't be resolved statically
+    fn resolve_template<'t>(
+        &self,
+        scope: &Rc<ModuleScope>,
+        tpl: &'t Tpl,
+        visiting: &mut FxHashSet<(PathBuf, String)>,
+    ) -> Result<ResolvedTemplate, &'t Expr> {
+        let mut content = String::new();
+        let mut line_map = Vec::new();
+
+        for (index, quasi) in tpl.quasis.iter().enumerate() {
+            line_map.push(LineMapping {
+                content_line: current_line(&content),
+                content_col: current_col(&content),
+                file_path: scope.file_path.clone(),
+                line_number: scope.line_of(quasi.span.lo()),
+                column_number: scope.col_of(quasi.span.lo()),
+            });
+            content.push_str(&quasi.raw);
+
+            let Some(expr) = tpl.exprs.get(index) else {
+                continue;
+            };
+
+            let resolved = self
+                .resolve_interpolation(scope, expr, visiting)
+                .ok_or(&**expr)?;
+
+            let (splice_line, splice_col) = (current_line(&content), current_col(&content));
+            line_map.extend(resolved.line_map.into_iter().map(|mapping| LineMapping {
+                content_line: mapping.content_line + splice_line - 1,
+                // Only the first line of the spliced content continues the current line
+                content_col: if mapping.content_line == 1 {
+                    mapping.content_col + splice_col - 1
+                } else {
+                    mapping.content_col
+                },
+                ..mapping
+            }));
+            content.push_str(&resolved.content);
+        }
+
+        Ok(ResolvedTemplate { content, line_map })
+    }
+
+    // Only identifiers (or namespace members) pointing to gql constants are statically known
+    fn resolve_interpolation(
+        &self,
+        scope: &Rc<ModuleScope>,
+        expr: &Expr,
+        visiting: &mut FxHashSet<(PathBuf, String)>,
+    ) -> Option<ResolvedTemplate> {
+        match unwrap_type_assertions(expr) {
+            Expr::Ident(ident) => self.resolve_identifier(scope, &ident.sym, visiting),
+            Expr::Member(member) => {
+                let (Expr::Ident(namespace), MemberProp::Ident(prop)) =
+                    (&*member.obj, &member.prop)
+                else {
+                    return None;
+                };
+                let import = scope.imports.get(namespace.sym.as_ref())?;
+                if import.name != "*" {
+                    return None;
+                }
+                let (module, local) = self.resolve_export(
+                    scope,
+                    &ImportBinding {
+                        source: import.source.clone(),
+                        name: prop.sym.to_string(),
+                    },
+                    0,
+                )?;
+                self.resolve_identifier(&module, &local, visiting)
+            }
+            _ => None,
+        }
+    }
+
+    // Looks up a local constant first, then follows imports to the defining module
+    fn resolve_identifier(
+        &self,
+        scope: &Rc<ModuleScope>,
+        name: &str,
+        visiting: &mut FxHashSet<(PathBuf, String)>,
+    ) -> Option<ResolvedTemplate> {
+        // Constants interpolating each other or modules importing a name from each other
+        // in a loop can never be resolved
+        let key = (scope.file_path.clone(), name.to_string());
+        if !visiting.insert(key.clone()) {
+            return None;
+        }
+
+        let resolved = match scope.templates.get(name) {
+            Some(constant) => self.resolve_template(scope, constant, visiting).ok(),
+            None => scope
+                .imports
+                .get(name)
+                .and_then(|import| self.resolve_export(scope, import, 0))
+                .and_then(|(module, local)| self.resolve_identifier(&module, &local, visiting)),
+        };
+        visiting.remove(&key);
+        resolved
+    }
+
+    // Finds the module and local binding behind an imported name, following re-exports
+    fn resolve_export(
+        &self,
+        from: &ModuleScope,
+        import: &ImportBinding,
+        depth: usize,
+    ) -> Option<(Rc<ModuleScope>, String)> {
+        if depth > MAX_EXPORT_DEPTH || import.name == "*" {
+            return None;
+        }
+
+        let module = self.load_module(&resolve_import(&from.file_path, &import.source)?)?;
+
+        match module.exports.get(&import.name) {
+            Some(ExportBinding::Local(local)) => return Some((module.clone(), local.clone())),
+            Some(ExportBinding::ReExport(binding)) => {
+                return self.resolve_export(&module, binding, depth + 1)
+            }
+            None => {}
+        }
+
+        if module.templates.contains_key(&import.name) || module.imports.contains_key(&import.name)
+        {
+            return Some((module.clone(), import.name.clone()));
+        }
+
+        // Index files commonly re-export everything from their siblings
+        module.star_exports.iter().find_map(|source| {
+            self.resolve_export(
+                &module,
+                &ImportBinding {
+                    source: source.clone(),
+                    name: import.name.clone(),
+                },
+                depth + 1,
+            )
+        })
+    }
+
+    fn load_module(&self, file_path: &Path) -> Option<Rc<ModuleScope>> {
+        if let Some(cached) = self.modules.borrow().get(file_path) {
+            return cached.clone();
+        }
+
+        // Unreadable or unparsable modules simply leave the interpolation unresolved
+        let scope = fs::read_to_string(file_path)
+            .ok()
+            .and_then(|source_code| {
+                parse_module(file_path, source_code, syntax_for(file_path)).ok()
+            })
+            .map(|(module, source_map, comments)| {
+                Rc::new(ModuleScope::collect(
+                    file_path.to_path_buf(),
+                    source_map,
+                    comments,
+                    &module,
+                    self.options,
+                ))
+            });
+
+        self.modules
+            .borrow_mut()
+            .insert(file_path.to_path_buf(), scope.clone());
+        scope
+    }
+}
+
+// 1-based line number the next appended character will land on
+fn current_line(content: &str) -> u32 {
+    content.matches('\n').count() as u32 + 1
+}
+
+// 1-based column the next appended character will land on
+fn current_col(content: &str) -> u32 {
+    let line_start = content.rfind('\n').map_or(0, |index| index + 1);
+    content[line_start..].chars().count() as u32 + 1
+}
+
+struct GraphQLVisitor<'a> {
+    options: &'a ExtractionOptions,
+    scope: Rc<ModuleScope>,
+    resolver: TemplateResolver<'a>,
+    // Names bound by enclosing declarations, parameters and blocks (innermost last)
+    local_bindings: Vec<Vec<String>>,
+    graphql_strings: Vec<GraphQLString>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> GraphQLVisitor<'a> {
     // Initialize visitor state for file processing
-    fn new(file_path: std::path::PathBuf, source_map: Rc<SourceMap>) -> Self {
+    fn new(scope: Rc<ModuleScope>, options: &'a ExtractionOptions) -> Self {
         Self {
-            file_path,
+            options,
+            scope,
+            resolver: TemplateResolver {
+                options,
+                modules: RefCell::default(),
+            },
+            local_bindings: Vec::new(),
             graphql_strings: Vec::new(),
-            source_map,
+            diagnostics: Vec::new(),
         }
     }
 
-    // Core extraction logic: identifies GraphQL templates and extracts static content
-    fn extract_graphql_from_tagged_template(&mut self, tpl: &TaggedTpl) {
-        // Check if this is a GraphQL tagged template (gql`...` or graphql`...`)
-        if let Expr::Ident(ident) = &*tpl.tag {
-            if ident.sym.as_ref() == "gql" || ident.sym.as_ref() == "graphql" {
-                // Skip dynamic templates - runtime values could change GraphQL structure
-                if tpl.tpl.quasis.len() > 1 {
+    // Import-aware mode: only tags imported from a configured module count, others are reported
+    fn is_trusted_tag(&mut self, tag: &Expr) -> bool {
+        let Some(modules) = &self.options.tag_modules else {
+            return true;
+        };
+        let Some(ident) = root_ident(tag) else {
+            return false;
+        };
+        let name = ident.sym.as_ref();
+
+        let (kind, message) = if self
+            .local_bindings
+            .iter()
+            .any(|bindings| bindings.iter().any(|binding| binding == name))
+        {
+            (
+                DiagnosticKind::ShadowedTag,
+                format!("`{name}` refers to a local binding instead of a GraphQL tag import"),
+            )
+        } else {
+            match self.scope.imports.get(name) {
+                Some(import) if self.options.is_tag_module(&import.source) => return true,
+                Some(import) => (
+                    DiagnosticKind::ForeignTag,
+                    format!(
+                        "`{name}` is imported from '{}' which is not a GraphQL module ({})",
+                        import.source,
+                        modules.join(", ")
+                    ),
+                ),
+                None => (
+                    DiagnosticKind::ForeignTag,
+                    format!(
+                        "`{name}` is not imported from a GraphQL module ({})",
+                        modules.join(", ")
+                    ),
+                ),
+            }
+        };
+
+        self.report(ident.span.lo(), kind, message);
+        false
+    }
+
+    fn report(&mut self, byte_pos: BytePos, kind: DiagnosticKind, message: String) {
+        let location = self.scope.source_map.lookup_char_pos(byte_pos);
+        self.diagnostics.push(Diagnostic {
+            file_path: self.scope.file_path.clone(),
+            line: location.line as u32,
+            col: location.col_display as u32 + 1,
+            kind,
+            phase: Phase::Extraction,
+            severity: Severity::Warning,
+            message,
+        });
+    }
+
+    // Tracks names declared in a scope while its children are visited
+    fn with_bindings(&mut self, bindings: Vec<String>, visit: impl FnOnce(&mut Self)) {
+        self.local_bindings.push(bindings);
+        visit(self);
+        self.local_bindings.pop();
+    }
+
+    // Core extraction logic: captures static content of a GraphQL template
+    fn extract_graphql_from_template(&mut self, tpl: &Tpl) {
+        let Some(first_quasi) = tpl.quasis.first() else {
+            return;
+        };
+        let line_number = self.scope.line_of(first_quasi.span.lo());
+        let column_number = self.scope.col_of(first_quasi.span.lo());
+
+        // Static templates are used as-is, interpolated ones are stitched from known constants
+        let (content, line_map) = if tpl.quasis.len() == 1 {
+            (first_quasi.raw.to_string(), Vec::new())
+        } else {
+            // Skip dynamic templates - runtime values could change GraphQL structure
+            match self
+                .resolver
+                .resolve_template(&self.scope, tpl, &mut FxHashSet::default())
+            {
+                Ok(resolved) => (resolved.content, resolved.line_map),
+                Err(interpolation) => {
+                    let snippet = self
+                        .scope
+                        .source_map
+                        .span_to_snippet(interpolation.span())
+                        .unwrap_or_default();
+                    self.report(
+                        interpolation.span().lo(),
+                        DiagnosticKind::DynamicTemplate,
+                        format!("template skipped: `${{{snippet}}}` can't be resolved statically"),
+                    );
                     return;
                 }
+            }
+        };
 
-                // Capture static GraphQL string with line number info for error reporting
-                if let Some(first_quasi) = tpl.tpl.quasis.first() {
-                    let content = first_quasi.raw.as_ref();
-                    let byte_pos = first_quasi.span.lo();
-                    let line_number = self.source_map.lookup_char_pos(byte_pos).line as u32;
-                    self.graphql_strings.push(GraphQLString {
-                        content: content.to_string(),
-                        file_path: self.file_path.clone(),
-                        line_number,
-                    });
+        // Capture GraphQL string with line number info for error reporting
+        self.graphql_strings.push(GraphQLString {
+            content,
+            file_path: self.scope.file_path.clone(),
+            line_number,
+            column_number,
+            line_map,
+        });
+    }
+}
+
+// SWC visitor pattern for AST traversal
+impl Visit for GraphQLVisitor<'_> {
+    // Automatically called by SWC for each expression in the AST
+    fn visit_expr(&mut self, expr: &Expr) {
+        // Check if this is a GraphQL template (gql`...`, Relay.QL`...` or graphql(`...`))
+        if let Some((tag, tpl)) = self.options.graphql_template(expr) {
+            if self.is_trusted_tag(tag) {
+                self.extract_graphql_from_template(&tpl);
+            }
+
+            // graphql(/* GraphQL */ `...`) must not be extracted a second time as magic comment
+            if let Expr::Call(call) = expr {
+                call.callee.visit_with(self);
+                for (index, arg) in call.args.iter().enumerate() {
+                    if index == 0 {
+                        arg.expr.visit_children_with(self);
+                    } else {
+                        arg.visit_with(self);
+                    }
                 }
+                return;
             }
+        } else if let Some(tpl) = self.scope.magic_comment_template(expr) {
+            self.extract_graphql_from_template(&tpl);
         }
+
+        // Ensure complete AST traversal for nested templates
+        expr.visit_children_with(self);
+    }
+
+    // Top-level declarations bind their names for the whole module
+    fn visit_module(&mut self, module: &Module) {
+        let mut bindings = Vec::new();
+        for item in &module.body {
+            match item {
+                ModuleItem::Stmt(Stmt::Decl(decl)) => collect_decl_bindings(decl, &mut bindings),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                    collect_decl_bindings(&export.decl, &mut bindings)
+                }
+                _ => {}
+            }
+        }
+        self.with_bindings(bindings, |visitor| module.visit_children_with(visitor));
+    }
+
+    fn visit_block_stmt(&mut self, block: &BlockStmt) {
+        let mut bindings = Vec::new();
+        for stmt in &block.stmts {
+            if let Stmt::Decl(decl) = stmt {
+                collect_decl_bindings(decl, &mut bindings);
+            }
+        }
+        self.with_bindings(bindings, |visitor| block.visit_children_with(visitor));
+    }
+
+    fn visit_function(&mut self, function: &Function) {
+        let mut bindings = Vec::new();
+        for param in &function.params {
+            collect_pat_bindings(&param.pat, &mut bindings);
+        }
+        self.with_bindings(bindings, |visitor| function.visit_children_with(visitor));
+    }
+
+    fn visit_arrow_expr(&mut self, arrow: &ArrowExpr) {
+        let mut bindings = Vec::new();
+        for param in &arrow.params {
+            collect_pat_bindings(param, &mut bindings);
+        }
+        self.with_bindings(bindings, |visitor| arrow.visit_children_with(visitor));
+    }
+
+    fn visit_catch_clause(&mut self, catch: &CatchClause) {
+        let mut bindings = Vec::new();
+        if let Some(param) = &catch.param {
+            collect_pat_bindings(param, &mut bindings);
+        }
+        self.with_bindings(bindings, |visitor| catch.visit_children_with(visitor));
     }
 }
 
-// SWC visitor pattern for AST traversal
-impl Visit for GraphQLVisitor {
-    // Automatically called by SWC for each tagged template in the AST
-    fn visit_tagged_tpl(&mut self, tpl: &TaggedTpl) {
-        // Check if this template contains GraphQL content
-        self.extract_graphql_from_tagged_template(tpl);
+// Names introduced by a declaration statement
+fn collect_decl_bindings(decl: &Decl, bindings: &mut Vec<String>) {
+    match decl {
+        Decl::Var(var) => {
+            for declarator in &var.decls {
+                collect_pat_bindings(&declarator.name, bindings);
+            }
+        }
+        Decl::Fn(function) => bindings.push(function.ident.sym.to_string()),
+        Decl::Class(class) => bindings.push(class.ident.sym.to_string()),
+        _ => {}
+    }
+}
 
-        // Ensure complete AST traversal for nested templates
-        tpl.visit_children_with(self);
+// Names introduced by a (possibly destructuring) binding pattern
+fn collect_pat_bindings(pat: &Pat, bindings: &mut Vec<String>) {
+    match pat {
+        Pat::Ident(ident) => bindings.push(ident.id.sym.to_string()),
+        Pat::Array(array) => {
+            for element in array.elems.iter().flatten() {
+                collect_pat_bindings(element, bindings);
+            }
+        }
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(key_value) => {
+                        collect_pat_bindings(&key_value.value, bindings)
+                    }
+                    ObjectPatProp::Assign(assign) => bindings.push(assign.key.sym.to_string()),
+                    ObjectPatProp::Rest(rest) => collect_pat_bindings(&rest.arg, bindings),
+                }
+            }
+        }
+        Pat::Assign(assign) => collect_pat_bindings(&assign.left, bindings),
+        Pat::Rest(rest) => collect_pat_bindings(&rest.arg, bindings),
+        _ => {}
     }
 }
 
@@ -166,9 +1056,7 @@ mod tests {
                 .filter_map(|entry| entry.ok())
                 .filter(|entry| {
                     let path = entry.path();
-                    path.is_file()
-                        && (path.extension() == Some(std::ffi::OsStr::new("ts"))
-                            || path.extension() == Some(std::ffi::OsStr::new("tsx")))
+                    path.is_file() && is_script_file(&path)
                 })
                 .collect();
 
@@ -183,7 +1071,13 @@ mod tests {
                         results.push(result);
                     }
                     Err(e) => {
-                        results.push(format!("File: {}\nError: {}\n\n", file_path.display(), e));
+                        let git_root = fixture_dir.parent().unwrap().parent().unwrap();
+                        let relative_path = file_path.strip_prefix(git_root).unwrap_or(&file_path);
+                        results.push(format!(
+                            "File: {}\nError: {}\n\n",
+                            relative_path.display(),
+                            e
+                        ));
                     }
                 }
             }
@@ -212,4 +1106,87 @@ mod tests {
         let result = test_fixture_directory("edge_cases");
         insta::assert_snapshot!(result);
     }
+
+    // Custom tag names replace the defaults and may be member expressions
+    #[test]
+    fn test_custom_tag_names() {
+        let file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("fixtures/edge_cases/tag_variants.ts");
+        let options = ExtractionOptions {
+            tag_names: vec!["Relay.QL".to_string()],
+            ..Default::default()
+        };
+
+        let extraction = extract_graphql_from_file_with_options(&file_path, &options).unwrap();
+        insta::assert_snapshot!(format_extraction_result(
+            &file_path,
+            &extraction.graphql_strings
+        ));
+    }
+
+    // Import-aware mode skips shadowed and foreign tags and reports them
+    #[test]
+    fn test_import_aware_tags() {
+        let file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("fixtures/edge_cases/import_aware_tags.ts");
+        let options = ExtractionOptions {
+            tag_modules: Some(DEFAULT_TAG_MODULES.iter().map(|m| m.to_string()).collect()),
+            ..Default::default()
+        };
+
+        let extraction = extract_graphql_from_file_with_options(&file_path, &options).unwrap();
+        let mut result = format_extraction_result(&file_path, &extraction.graphql_strings);
+        result.push_str("Diagnostics:\n");
+        for diagnostic in &extraction.diagnostics {
+            result.push_str(&format!("{diagnostic}\n"));
+        }
+        insta::assert_snapshot!(result);
+    }
+
+    // Modules re-exporting a name from each other leave the template unresolved
+    #[test]
+    fn test_circular_reexports() {
+        let file_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("fixtures/edge_cases/circular_reexports.ts");
+
+        let extraction =
+            extract_graphql_from_file_with_options(&file_path, &ExtractionOptions::default())
+                .unwrap();
+        assert!(extraction.graphql_strings.is_empty());
+        assert_eq!(extraction.diagnostics.len(), 1);
+        assert_eq!(
+            extraction.diagnostics[0].kind,
+            DiagnosticKind::DynamicTemplate
+        );
+    }
+
+    // Files whose only GraphQL is marked by a differently cased magic comment aren't skipped
+    #[test]
+    fn test_magic_comment_case_insensitive() {
+        let file_path = PathBuf::from("magic_comment_case.ts");
+        let source_code =
+            "export const Q = /* GRAPHQL */ `query UpperCaseComment @catch { viewer { id } }`;\n";
+        let options = ExtractionOptions {
+            tag_names: vec!["Relay.QL".to_string()],
+            ..Default::default()
+        };
+
+        let extraction = extract_graphql_from_source(
+            &file_path,
+            source_code.to_string(),
+            syntax_for(&file_path),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(extraction.graphql_strings.len(), 1);
+        assert!(extraction.graphql_strings[0]
+            .content
+            .contains("query UpperCaseComment"));
+    }
 }
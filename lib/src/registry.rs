@@ -3,23 +3,28 @@
 //! Uses DashMap for thread-safe concurrent access during parallel file parsing.
 use anyhow::Result;
 use dashmap::DashMap;
-use globset::{Glob, GlobSetBuilder};
+use globset::{Glob, GlobSet, GlobSetBuilder};
 use ignore::{WalkBuilder, WalkState};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
-use std::sync::{atomic::AtomicUsize, Arc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{atomic::AtomicUsize, Arc, Mutex};
 
+use crate::code_frame::code_frame;
+use crate::diagnostics::{error_diagnostic, Diagnostic, Phase};
+use crate::parsers::extract_graphql;
 use crate::parsers::graphql_parser::{
-    parse_graphql_to_ast, FragmentDefinition, GraphQLItem, QueryOperation,
+    parse_graphql_to_ast_with_policy, FragmentDefinition, GraphQLItem, QueryOperation,
 };
-use crate::parsers::typescript_parser::extract_graphql_from_file;
+use crate::parsers::typescript_parser::ExtractionOptions;
 
 // Thread-safe storage for reusable GraphQL fragments
-pub type FragmentRegistry = Arc<DashMap<String, FragmentDefinition>>;
+// Every definition of a name is kept (sorted by location) so duplicates can be reported
+pub type FragmentRegistry = Arc<DashMap<String, Vec<FragmentDefinition>>>;
 
-// Thread-safe storage for main GraphQL operations
-pub type QueryRegistry = Arc<DashMap<String, QueryOperation>>;
+// Thread-safe storage for main GraphQL operations, keyed by `QueryOperation::id`
+pub type QueryRegistry = Arc<DashMap<String, Vec<QueryOperation>>>;
 
 // Central store combining fragments and queries for validation
 #[derive(Serialize, Deserialize)]
@@ -30,6 +35,12 @@ pub struct GraphQLRegistry {
     pub queries: QueryRegistry,
     #[serde(skip)]
     pub file_count: usize,
+    // Findings about skipped GraphQL, sorted by location once processing is done
+    #[serde(skip)]
+    pub diagnostics: Mutex<Vec<Diagnostic>>,
+    // Content of every file defining GraphQL, for code frames of findings
+    #[serde(skip)]
+    pub sources: Arc<DashMap<PathBuf, String>>,
 }
 
 // DashMap doesn't implement Serialize directly - need custom conversion
@@ -81,18 +92,72 @@ impl GraphQLRegistry {
             file_count: 0,
             fragments: Arc::new(DashMap::new()),
             queries: Arc::new(DashMap::new()),
+            diagnostics: Mutex::new(Vec::new()),
+            sources: Arc::new(DashMap::new()),
+        }
+    }
+
+    // Number of operation definitions (duplicates included)
+    pub fn query_count(&self) -> usize {
+        self.queries.iter().map(|entry| entry.value().len()).sum()
+    }
+
+    // Number of fragment definitions (duplicates included)
+    pub fn fragment_count(&self) -> usize {
+        self.fragments.iter().map(|entry| entry.value().len()).sum()
+    }
+
+    // Definition used to resolve `...name` - the first one by location if the name is defined twice
+    pub fn fragment(&self, name: &str) -> Option<FragmentDefinition> {
+        self.fragments
+            .get(name)
+            .and_then(|definitions| definitions.first().cloned())
+    }
+
+    // Excerpt of a file around a 1-based position, empty if the file isn't known
+    pub fn code_frame(&self, file_path: &Path, line: u32, col: Option<u32>) -> String {
+        self.sources
+            .get(file_path)
+            .map(|source| code_frame(source.value(), line, col))
+            .unwrap_or_default()
+    }
+
+    // Parallel processing collects definitions and diagnostics in arbitrary order
+    fn finish(&mut self) {
+        for mut definitions in self.queries.iter_mut() {
+            definitions.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+            // Interpolated and `#import`ed definitions are parsed once per document using them
+            definitions.dedup_by(|a, b| a.file_path == b.file_path && a.line == b.line);
+        }
+        for mut definitions in self.fragments.iter_mut() {
+            definitions.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+            definitions.dedup_by(|a, b| a.file_path == b.file_path && a.line == b.line);
         }
+
+        self.diagnostics
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .sort();
     }
 }
 
 // Parallel processing of file lists using rayon for performance
 pub fn process_files(files: &[String]) -> GraphQLRegistry {
-    let registry = GraphQLRegistry::new();
+    process_files_with_options(files, &ExtractionOptions::default())
+}
+
+// Same as `process_files` with custom extraction options (e.g. tag names)
+pub fn process_files_with_options(
+    files: &[String],
+    options: &ExtractionOptions,
+) -> GraphQLRegistry {
+    let mut registry = GraphQLRegistry::new();
 
     files.par_iter().for_each(|file| {
-        parse_file(Path::new(file), &registry);
+        parse_file(Path::new(file), &registry, options);
     });
 
+    registry.finish();
     registry
 }
 
@@ -101,6 +166,21 @@ pub fn process_glob(
     root_path: &Path,
     include_patterns: &[&str], // e.g. &["**/*.ts", "**/*.tsx"]
     exclude_patterns: &[&str], // e.g. &["**/node_modules/**"]
+) -> Result<GraphQLRegistry> {
+    process_glob_with_options(
+        root_path,
+        include_patterns,
+        exclude_patterns,
+        &ExtractionOptions::default(),
+    )
+}
+
+// Same as `process_glob` with custom extraction options (e.g. tag names)
+pub fn process_glob_with_options(
+    root_path: &Path,
+    include_patterns: &[&str],
+    exclude_patterns: &[&str],
+    options: &ExtractionOptions,
 ) -> Result<GraphQLRegistry> {
     // Build include GlobSet
     let mut include_builder = GlobSetBuilder::new();
@@ -116,6 +196,11 @@ pub fn process_glob(
     }
     let exclude_set = Arc::new(exclude_builder.build()?);
 
+    // Config globs are anchored at the config directory and need the absolute path to match
+    let absolute_root = root_path
+        .canonicalize()
+        .unwrap_or_else(|_| root_path.to_path_buf());
+
     let mut registry = GraphQLRegistry::new();
     let registry_ref = &registry;
     let file_count = Arc::new(AtomicUsize::new(0));
@@ -128,14 +213,19 @@ pub fn process_glob(
             let exclude = Arc::clone(&exclude_set);
             let registry = registry_ref;
             let file_counter = Arc::clone(&file_count);
+            let absolute_root = &absolute_root;
 
             Box::new(move |entry_res: Result<ignore::DirEntry, ignore::Error>| {
                 if let Ok(entry) = entry_res {
                     let path = entry.path();
-                    if path.is_dir() && exclude.is_match(path) {
+                    let absolute_path =
+                        absolute_root.join(path.strip_prefix(root_path).unwrap_or(path));
+                    let is_match =
+                        |globs: &GlobSet| globs.is_match(path) || globs.is_match(&absolute_path);
+                    if path.is_dir() && is_match(&exclude) {
                         return WalkState::Skip;
-                    } else if path.is_file() && include.is_match(path) {
-                        parse_file(path, registry);
+                    } else if path.is_file() && is_match(&include) {
+                        parse_file(path, registry, options);
                         file_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
                     }
                 }
@@ -144,36 +234,80 @@ pub fn process_glob(
         });
 
     registry.file_count = file_count.load(std::sync::atomic::Ordering::Relaxed);
+    registry.finish();
 
     Ok(registry)
 }
 
-fn parse_file(file: &Path, registry: &GraphQLRegistry) {
-    let graphql_strings_result = extract_graphql_from_file(Path::new(file));
-    if let Ok(graphql_strings) = graphql_strings_result {
-        for graphql_string in &graphql_strings {
-            let graphql_ast = parse_graphql_to_ast(graphql_string);
-            if let Ok(ast) = graphql_ast {
-                for graphql_item in ast {
-                    match graphql_item {
-                        GraphQLItem::Fragment(fragment) => {
-                            registry.fragments.insert(fragment.name.clone(), fragment);
-                        }
-                        GraphQLItem::Query(query) => {
-                            registry.queries.insert(query.name.clone(), query);
+fn parse_file(file: &Path, registry: &GraphQLRegistry, options: &ExtractionOptions) {
+    let mut diagnostics = Vec::new();
+
+    match extract_graphql(file, options) {
+        Ok(extraction) => {
+            diagnostics.extend(extraction.diagnostics);
+            for graphql_string in &extraction.graphql_strings {
+                match parse_graphql_to_ast_with_policy(graphql_string, options.ignore_policy) {
+                    Ok(ast) => {
+                        for graphql_item in ast {
+                            match graphql_item {
+                                GraphQLItem::Fragment(fragment) => {
+                                    registry
+                                        .fragments
+                                        .entry(fragment.name.clone())
+                                        .or_default()
+                                        .push(fragment);
+                                }
+                                GraphQLItem::Query(query) => {
+                                    registry.queries.entry(query.id()).or_default().push(query);
+                                }
+                            }
                         }
                     }
+                    // A typo must not silently exempt a document from validation
+                    Err(error) => diagnostics.push(error_diagnostic(file, error, Phase::GraphQL)),
+                }
+            }
+            // Imported and interpolated fragments may live in files outside of the scanned paths
+            let origins = extraction
+                .graphql_strings
+                .iter()
+                .flat_map(|graphql_string| {
+                    std::iter::once(&graphql_string.file_path).chain(
+                        graphql_string
+                            .line_map
+                            .iter()
+                            .map(|mapping| &mapping.file_path),
+                    )
+                });
+            for path in origins {
+                if path != file && !registry.sources.contains_key(path) {
+                    if let Ok(source) = fs::read_to_string(path) {
+                        registry.sources.insert(path.clone(), source);
+                    }
                 }
             }
+            if !extraction.graphql_strings.is_empty() {
+                registry
+                    .sources
+                    .insert(file.to_path_buf(), extraction.source);
+            }
         }
-    } else {
-        eprintln!("Failed to parse GraphQL from file: {}", file.display());
+        Err(error) => diagnostics.push(error_diagnostic(file, error, Phase::Extraction)),
+    }
+
+    if !diagnostics.is_empty() {
+        registry
+            .diagnostics
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .extend(diagnostics);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::diagnostics::{promote_to_errors, DiagnosticKind, Severity};
     use crate::tree_formatter::TreeFormatter;
     use std::fs;
     use std::path::PathBuf;
@@ -191,22 +325,34 @@ mod tests {
         formatter.add_line(0, "GraphQL Registry");
 
         // Format queries section
-        let query_count = registry.queries.len();
+        let query_count = registry.query_count();
         formatter.add_line(1, &format!("Queries ({})", query_count));
 
         if query_count > 0 {
             // Sort queries by name for consistent output
-            let mut queries: Vec<_> = registry.queries.iter().collect();
-            queries.sort_by(|a, b| a.key().cmp(b.key()));
+            let mut queries: Vec<_> = registry
+                .queries
+                .iter()
+                .flat_map(|entry| entry.value().clone())
+                .collect();
+            queries.sort_by(|a, b| {
+                (&a.name, &a.file_path, a.line).cmp(&(&b.name, &b.file_path, b.line))
+            });
 
-            for entry in queries {
-                let name = entry.key();
-                let query = entry.value();
+            for query in &queries {
                 let relative_path = query
                     .file_path
                     .strip_prefix(&git_root)
                     .unwrap_or(&query.file_path);
-                formatter.add_line(2, &format!("{} ({})", name, relative_path.display()));
+                formatter.add_line(
+                    2,
+                    &format!(
+                        "{} ({}:{})",
+                        query.name,
+                        relative_path.display(),
+                        query.line
+                    ),
+                );
 
                 // Query directives
                 if !query.directives.is_empty() {
@@ -282,22 +428,34 @@ mod tests {
         }
 
         // Format fragments section
-        let fragment_count = registry.fragments.len();
+        let fragment_count = registry.fragment_count();
         formatter.add_line(1, &format!("Fragments ({})", fragment_count));
 
         if fragment_count > 0 {
             // Sort fragments by name for consistent output
-            let mut fragments: Vec<_> = registry.fragments.iter().collect();
-            fragments.sort_by(|a, b| a.key().cmp(b.key()));
+            let mut fragments: Vec<_> = registry
+                .fragments
+                .iter()
+                .flat_map(|entry| entry.value().clone())
+                .collect();
+            fragments.sort_by(|a, b| {
+                (&a.name, &a.file_path, a.line).cmp(&(&b.name, &b.file_path, b.line))
+            });
 
-            for entry in fragments {
-                let name = entry.key();
-                let fragment = entry.value();
+            for fragment in &fragments {
                 let relative_path = fragment
                     .file_path
                     .strip_prefix(&git_root)
                     .unwrap_or(&fragment.file_path);
-                formatter.add_line(2, &format!("{} ({})", name, relative_path.display()));
+                formatter.add_line(
+                    2,
+                    &format!(
+                        "{} ({}:{})",
+                        fragment.name,
+                        relative_path.display(),
+                        fragment.line
+                    ),
+                );
 
                 // Fragment directives
                 if !fragment.directives.is_empty() {
@@ -390,9 +548,7 @@ mod tests {
                 .filter_map(|entry| entry.ok())
                 .filter(|entry| {
                     let path = entry.path();
-                    path.is_file()
-                        && (path.extension() == Some(std::ffi::OsStr::new("ts"))
-                            || path.extension() == Some(std::ffi::OsStr::new("tsx")))
+                    path.is_file() && crate::parsers::is_supported_file(&path)
                 })
                 .collect();
 
@@ -433,4 +589,34 @@ mod tests {
         let formatted = format_registry_with_tree_formatter(&registry);
         insta::assert_snapshot!(formatted);
     }
+
+    // Skipped templates and unresolved imports are kept, sorted by location
+    #[test]
+    fn test_registry_diagnostics_from_edge_case_fixtures() {
+        let files = collect_fixture_files("edge_cases");
+        let registry = process_files(&files);
+        let diagnostics = registry.diagnostics.lock().unwrap();
+        let formatted = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        insta::assert_snapshot!(formatted);
+    }
+
+    // Schema documents are reported as skipped, not as parse errors that could fail validation
+    #[test]
+    fn test_schema_documents_are_reported_as_skipped() {
+        let file = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("fixtures/edge_cases/schema.graphql");
+        let registry = process_files(&[file.to_string_lossy().to_string()]);
+        let mut diagnostics = registry.diagnostics.lock().unwrap().clone();
+        promote_to_errors(&mut diagnostics, DiagnosticKind::ParseError);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, DiagnosticKind::SkippedSchema);
+        assert_eq!(diagnostics[0].severity, Severity::Info);
+    }
 }
@@ -8,13 +8,14 @@ use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::parsers::graphql_parser::{Directive, Selection};
+use crate::parsers::graphql_parser::{Directive, OperationKind, Selection};
 use crate::registry::GraphQLRegistry;
 
 // Query with all fragment spreads replaced by actual fragment content
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct QueryWithFragments {
     pub name: String,
+    pub kind: OperationKind,
     pub file_path: PathBuf,
     pub directives: Vec<Directive>,
     pub selections: Vec<Selection>, // Now hierarchical
@@ -32,32 +33,34 @@ pub struct FragmentNode {
 // Main entry point: expands all fragment dependencies for validation
 // Transforms queries from flat registry into complete hierarchical structures
 pub fn registry_to_dependency_graph(registry: &GraphQLRegistry) -> Result<Vec<QueryWithFragments>> {
+    // Deterministic ordering for reliable snapshots, diffs and the first reported error
+    let mut queries: Vec<_> = registry
+        .queries
+        .iter()
+        .flat_map(|entry| entry.value().clone())
+        .collect();
+    queries.sort_by(|a, b| (&a.name, &a.file_path, a.line).cmp(&(&b.name, &b.file_path, b.line)));
+
+    // Process each query in the registry (including every definition of a duplicated name)
     let mut result = Vec::new();
-
-    // Process each query in the registry
-    for query_entry in registry.queries.iter() {
-        let query_name = query_entry.key();
-        let query = query_entry.value();
-
+    for query in queries {
         // Expand fragment spreads into complete dependency tree
         let resolved_selections = resolve_selections_with_fragments(
             &query.selections,
             registry,
             &mut FxHashSet::default(),
         )
-        .with_context(|| format!("Failed to resolve selections for query '{query_name}'"))?;
+        .with_context(|| format!("Failed to resolve selections for query '{}'", query.name))?;
 
         result.push(QueryWithFragments {
-            name: query.name.clone(),
-            file_path: query.file_path.clone(),
-            directives: query.directives.clone(),
+            name: query.name,
+            kind: query.kind,
+            file_path: query.file_path,
+            directives: query.directives,
             selections: resolved_selections,
         });
     }
 
-    // Deterministic ordering for reliable snapshots and diffs
-    result.sort_by(|a, b| a.name.cmp(&b.name));
-
     Ok(result)
 }
 
@@ -90,19 +93,15 @@ fn resolve_selections_with_fragments(
             }
             Selection::FragmentSpread(spread) => {
                 // Replace ...FragmentName with actual fragment selections
-                let fragment_entry = registry
-                    .fragments
-                    .get(&spread.name)
+                let fragment = registry
+                    .fragment(&spread.name)
                     .with_context(|| format!("Fragment '{}' not found in registry", spread.name))?;
 
-                let fragment = fragment_entry.value();
-
-                // Prevent infinite recursion from fragment cycles
+                // Prevent infinite recursion from fragment cycles - they are reported by
+                // `find_fragment_cycles`, the repeated spread is kept unexpanded
                 if visiting.contains(&spread.name) {
-                    return Err(anyhow::anyhow!(
-                        "Circular dependency detected involving fragment '{}'",
-                        spread.name
-                    ));
+                    resolved_selections.push(Selection::FragmentSpread(spread.clone()));
+                    continue;
                 }
 
                 // Track current path to detect cycles
@@ -196,7 +195,7 @@ mod tests {
             // Query selections (hierarchical)
             if !query.selections.is_empty() {
                 formatter.add_line(2, "Selections:");
-                format_selections(&mut formatter, &query.selections, 3, &git_root);
+                format_selections(&mut formatter, &query.selections, 3);
             }
         }
 
@@ -204,12 +203,7 @@ mod tests {
     }
 
     // Visual representation maintains query structure hierarchy
-    fn format_selections(
-        formatter: &mut TreeFormatter,
-        selections: &[Selection],
-        depth: usize,
-        git_root: &PathBuf,
-    ) {
+    fn format_selections(formatter: &mut TreeFormatter, selections: &[Selection], depth: usize) {
         for selection in selections {
             match selection {
                 Selection::Field(field_selection) => {
@@ -230,12 +224,7 @@ mod tests {
 
                     // Maintain visual hierarchy for nested structures
                     if !field_selection.selections.is_empty() {
-                        format_selections(
-                            formatter,
-                            &field_selection.selections,
-                            depth + 1,
-                            git_root,
-                        );
+                        format_selections(formatter, &field_selection.selections, depth + 1);
                     }
                 }
                 Selection::FragmentSpread(spread) => {
@@ -282,7 +271,7 @@ mod tests {
 
                     // Show expanded fragment structure
                     if !inline.selections.is_empty() {
-                        format_selections(formatter, &inline.selections, depth + 1, git_root);
+                        format_selections(formatter, &inline.selections, depth + 1);
                     }
                 }
             }
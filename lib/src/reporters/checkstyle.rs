@@ -0,0 +1,133 @@
+//! Checkstyle XML report grouped by file, as read by lint report aggregators
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::reporters::{escape_xml, line, relative_path};
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+// `<error>` element with its position for ordering within the file
+type Entry = (u32, Option<u32>, String);
+
+// Paths are relative to `root`, usually the working directory
+pub fn to_checkstyle(result: &ValidationResult, diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut files: BTreeMap<String, Vec<Entry>> = BTreeMap::new();
+    for error in result.errors.iter().chain(&result.warnings) {
+        files
+            .entry(relative_path(error.context.owner_file(), root))
+            .or_default()
+            .push((line(error), error.context.col, violation(error)));
+    }
+    // Documents that couldn't be analyzed use the diagnostic kind as source
+    for diagnostic in diagnostics {
+        let message = format!("{}: {}", diagnostic.label(), diagnostic.message);
+        files
+            .entry(relative_path(&diagnostic.file_path, root))
+            .or_default()
+            .push((
+                diagnostic.line,
+                Some(diagnostic.col),
+                element(
+                    diagnostic.line,
+                    Some(diagnostic.col),
+                    diagnostic.severity,
+                    &message,
+                    diagnostic.kind.code(),
+                ),
+            ));
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<checkstyle version=\"4.3\">\n");
+    for (file, mut entries) in files {
+        entries.sort();
+        xml.push_str(&format!("  <file name=\"{}\">\n", escape_xml(&file)));
+        for (_, _, element) in entries {
+            xml.push_str(&element);
+        }
+        xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>\n");
+    xml
+}
+
+fn violation(error: &ValidationError) -> String {
+    let message = format!(
+        "{} ({}: {})",
+        error.reason(),
+        error.label(),
+        error.context.query_name
+    );
+    element(
+        line(error),
+        error.context.col,
+        error.severity,
+        &message,
+        error.error_type.code(),
+    )
+}
+
+fn element(line: u32, col: Option<u32>, severity: Severity, message: &str, source: &str) -> String {
+    let column = col
+        .map(|col| format!(" column=\"{col}\""))
+        .unwrap_or_default();
+    let severity = match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    };
+    format!(
+        "    <error line=\"{line}\"{column} severity=\"{severity}\" message=\"{}\" source=\"gql-safeguard.{source}\"/>\n",
+        escape_xml(message)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{DiagnosticKind, Phase};
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_checkstyle_report() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/checkout_mutation.ts",
+            "invalid/partial_protection.ts",
+            "edge_cases/duplicate_names.ts",
+            "valid/legacy_component.jsx",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        insta::assert_snapshot!(to_checkstyle(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        ));
+    }
+
+    // Names and messages end up in attributes, paths may contain XML syntax as well
+    #[test]
+    fn test_checkstyle_escapes_xml() {
+        let registry = process_files(&fixture_files(&["invalid/missing_catch.tsx"]));
+        let mut result = validate_registry(&registry);
+        result.errors[0].context.query_name = "Odd<&>\"'Query".to_string();
+        let diagnostic = Diagnostic {
+            file_path: git_root().join("src/<draft> & \"copy\".ts"),
+            line: 1,
+            col: 2,
+            kind: DiagnosticKind::ParseError,
+            phase: Phase::Extraction,
+            severity: Severity::Error,
+            message: "Expected '>' after <Tag".to_string(),
+        };
+
+        let xml = to_checkstyle(&result, &[diagnostic], git_root());
+        assert!(xml.contains("Odd&lt;&amp;&gt;&quot;&apos;Query"));
+        assert!(xml.contains("<file name=\"src/&lt;draft&gt; &amp; &quot;copy&quot;.ts\">"));
+        assert!(xml.contains("Expected &apos;&gt;&apos; after &lt;Tag"));
+        assert!(!xml.contains("Odd<"));
+    }
+}
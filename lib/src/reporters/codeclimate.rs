@@ -0,0 +1,158 @@
+//! GitLab Code Quality report (a subset of the Code Climate issue format) for merge request diffs
+
+use rustc_hash::FxHashMap;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::baseline::{fingerprint, hash};
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::reporters::relative_path;
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+pub fn to_codeclimate(result: &ValidationResult, diagnostics: &[Diagnostic], root: &Path) -> Value {
+    // GitLab compares fingerprints of the source and target branch to tell new issues from
+    // resolved ones, so they reuse the line independent baseline fingerprint. Repeated identical
+    // violations get a counter to stay unique.
+    let mut occurrences: FxHashMap<String, usize> = FxHashMap::default();
+    let mut unique = |fingerprint: String| {
+        let occurrence = occurrences.entry(fingerprint.clone()).or_default();
+        *occurrence += 1;
+        if *occurrence == 1 {
+            fingerprint
+        } else {
+            format!("{fingerprint}-{occurrence}")
+        }
+    };
+
+    let mut issues: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let key = format!(
+                "{}\u{0}{}\u{0}{}",
+                diagnostic.kind.code(),
+                relative_path(&diagnostic.file_path, root),
+                diagnostic.message
+            );
+            diagnostic_issue(diagnostic, unique(hash(&key)), root)
+        })
+        .collect();
+    for error in result.errors.iter().chain(&result.warnings) {
+        issues.push(issue(error, unique(fingerprint(error)), root));
+    }
+    json!(issues)
+}
+
+// Documents that couldn't be analyzed, e.g. syntax errors or dynamic templates
+fn diagnostic_issue(diagnostic: &Diagnostic, fingerprint: String, root: &Path) -> Value {
+    json!({
+        "type": "issue",
+        "check_name": diagnostic.kind.code(),
+        "description": format!("{}: {}", diagnostic.label(), diagnostic.message),
+        "categories": ["Bug Risk"],
+        "severity": severity(diagnostic.severity),
+        "fingerprint": fingerprint,
+        "location": {
+            "path": relative_path(&diagnostic.file_path, root),
+            "positions": { "begin": { "line": diagnostic.line, "column": diagnostic.col } },
+        },
+    })
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+        Severity::Info => "info",
+    }
+}
+
+fn issue(error: &ValidationError, fingerprint: String, root: &Path) -> Value {
+    let path = relative_path(error.context.owner_file(), root);
+    // GitLab needs at least a line, registry-wide findings without one point at the file
+    let line = error.context.line.unwrap_or(1);
+    let location = match error.context.col {
+        Some(col) => json!({
+            "path": path,
+            "positions": { "begin": { "line": line, "column": col } },
+        }),
+        None => json!({
+            "path": path,
+            "lines": { "begin": line },
+        }),
+    };
+
+    let mut body = format!("{}: {}", error.label(), error.context.query_name);
+    if !error.context.location_path.is_empty() {
+        body.push_str(&format!(" at {}", error.context.location_path));
+    }
+    if !error.explanation.is_empty() {
+        body.push_str(&format!("\n\n{}", error.explanation));
+    }
+
+    json!({
+        "type": "issue",
+        "check_name": error.error_type.code(),
+        "description": format!("{} [{}]: {}", error.error_type, error.error_type.code(), error.reason()),
+        "content": { "body": body },
+        "categories": ["Bug Risk"],
+        "severity": severity(error.severity),
+        "fingerprint": fingerprint,
+        "location": location,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_codeclimate_report() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/partial_protection.ts",
+            "invalid/checkout_mutation.ts",
+            "edge_cases/duplicate_names.ts",
+            "valid/legacy_component.jsx",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        let report = to_codeclimate(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        );
+        insta::assert_snapshot!(serde_json::to_string_pretty(&report).unwrap());
+    }
+
+    // GitLab drops issues with the same fingerprint, repeated violations and diagnostics stay apart
+    #[test]
+    fn test_codeclimate_fingerprints_are_unique() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/partial_protection.ts",
+            "edge_cases/graphql_syntax_error.ts",
+        ]));
+        let mut result = validate_registry(&registry);
+        result.errors.push(result.errors[0].clone());
+        let mut diagnostics = registry.diagnostics.lock().unwrap().clone();
+        diagnostics.push(diagnostics[0].clone());
+
+        let report = to_codeclimate(&result, &diagnostics, git_root());
+        let fingerprints: Vec<&str> = report
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|issue| issue["fingerprint"].as_str().unwrap())
+            .collect();
+        let mut unique = fingerprints.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), fingerprints.len());
+        assert_eq!(
+            fingerprints.len(),
+            result.errors.len() + result.warnings.len() + diagnostics.len()
+        );
+        assert!(fingerprints.contains(&format!("{}-2", fingerprint(&result.errors[0])).as_str()));
+    }
+}
@@ -0,0 +1,122 @@
+//! One `path:line:col: severity [code] message` line per violation for editors and grep
+
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::reporters::{line, relative_path};
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+// Ordered by position so quickfix lists step through files top to bottom
+pub fn to_compact(result: &ValidationResult, diagnostics: &[Diagnostic], root: &Path) -> String {
+    let mut lines: Vec<(String, u32, u32, String)> = result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|error| {
+            (
+                relative_path(error.context.owner_file(), root),
+                line(error),
+                error.context.col.unwrap_or(1),
+                message(error),
+            )
+        })
+        .collect();
+    // Documents that couldn't be analyzed, named by the diagnostic kind instead of a rule code
+    lines.extend(diagnostics.iter().map(|diagnostic| {
+        (
+            relative_path(&diagnostic.file_path, root),
+            diagnostic.line,
+            diagnostic.col,
+            format!(
+                "{} [{}] {}: {}",
+                severity(diagnostic.severity),
+                diagnostic.kind.code(),
+                diagnostic.label(),
+                diagnostic.message
+            ),
+        )
+    }));
+    lines.sort();
+
+    lines
+        .into_iter()
+        .map(|(path, line, col, message)| format!("{path}:{line}:{col}: {message}\n"))
+        .collect()
+}
+
+fn message(error: &ValidationError) -> String {
+    format!(
+        "{} [{}] {} ({} {})",
+        severity(error.severity),
+        error.error_type.code(),
+        error.reason(),
+        error.label(),
+        error.context.query_name
+    )
+}
+
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_compact_report() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/checkout_mutation.ts",
+            "invalid/unprotected_nested.ts",
+            "edge_cases/duplicate_names.ts",
+            "edge_cases/unknown_fragments.ts",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        insta::assert_snapshot!(to_compact(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        ));
+    }
+
+    // Quickfix lists need `path:line:col: ` before the message, sorted by position
+    #[test]
+    fn test_compact_lines_are_sorted_positions() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/unprotected_nested.ts",
+            "invalid/checkout_mutation.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        let report = to_compact(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        );
+
+        let positions: Vec<(String, u32, u32)> = report
+            .lines()
+            .map(|line| {
+                let mut parts = line.splitn(4, ':');
+                let path = parts.next().unwrap().to_string();
+                let line_number = parts.next().unwrap().parse().unwrap();
+                let col = parts.next().unwrap().parse().unwrap();
+                assert!(parts.next().unwrap().starts_with(' '));
+                (path, line_number, col)
+            })
+            .collect();
+        let mut sorted = positions.clone();
+        sorted.sort();
+        assert_eq!(positions, sorted);
+        assert!(positions
+            .iter()
+            .any(|(path, _, _)| path == "fixtures/edge_cases/typescript_syntax_error.ts"));
+    }
+}
@@ -0,0 +1,157 @@
+//! GitHub Actions workflow commands, shown as annotations on pull request diffs
+
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::reporters::relative_path;
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+// One `::error` or `::warning` command per line
+pub fn to_github_annotations(
+    result: &ValidationResult,
+    diagnostics: &[Diagnostic],
+    root: &Path,
+) -> String {
+    let violations = result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|error| annotation(error, root));
+    diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic_annotation(diagnostic, root))
+        .chain(violations)
+        .map(|annotation| annotation + "\n")
+        .collect()
+}
+
+// Documents that couldn't be analyzed, e.g. syntax errors or dynamic templates
+fn diagnostic_annotation(diagnostic: &Diagnostic, root: &Path) -> String {
+    format!(
+        "::{} file={},line={},col={},title={}::{}",
+        command(diagnostic.severity),
+        escape_property(&relative_path(&diagnostic.file_path, root)),
+        diagnostic.line,
+        diagnostic.col,
+        escape_property(&diagnostic.label()),
+        escape_data(&diagnostic.message)
+    )
+}
+
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice",
+    }
+}
+
+fn annotation(error: &ValidationError, root: &Path) -> String {
+    let command = command(error.severity);
+
+    let mut properties = vec![format!(
+        "file={}",
+        escape_property(&relative_path(error.context.owner_file(), root))
+    )];
+    if let Some(line) = error.context.line {
+        properties.push(format!("line={line}"));
+    }
+    if let Some(col) = error.context.col {
+        properties.push(format!("col={col}"));
+    }
+    properties.push(format!(
+        "title={}",
+        escape_property(&format!(
+            "{} [{}]",
+            error.error_type,
+            error.error_type.code()
+        ))
+    ));
+
+    // Same details as the terminal output without the query tree
+    let mut message = format!(
+        "{}\n{}: {}",
+        error.reason(),
+        error.label(),
+        error.context.query_name
+    );
+    if !error.context.location_path.is_empty() {
+        message.push_str(&format!(" at {}", error.context.location_path));
+    }
+    if !error.explanation.is_empty() {
+        message.push_str(&format!("\n{}", error.explanation));
+    }
+
+    format!(
+        "::{command} {}::{}",
+        properties.join(","),
+        escape_data(&message)
+    )
+}
+
+// Messages may span lines, the command itself may not
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{DiagnosticKind, Phase};
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_github_annotations() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/unprotected_nested.ts",
+            "invalid/partial_protection.ts",
+            "edge_cases/circular_fragments.ts",
+            "valid/legacy_component.jsx",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        insta::assert_snapshot!(to_github_annotations(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        ));
+    }
+
+    // Workflow commands end at the line break and at the `::` after the properties
+    #[test]
+    fn test_github_escapes_commands() {
+        let diagnostic = Diagnostic {
+            file_path: git_root().join("src/a:b,c.ts"),
+            line: 1,
+            col: 1,
+            kind: DiagnosticKind::DynamicTemplate,
+            phase: Phase::Extraction,
+            severity: Severity::Warning,
+            message: "first line\r\nsecond line::100%".to_string(),
+        };
+
+        let annotations =
+            to_github_annotations(&ValidationResult::new(), &[diagnostic], git_root());
+        assert_eq!(
+            annotations,
+            "::warning file=src/a%3Ab%2Cc.ts,line=1,col=1,title=Dynamic GraphQL template::first line%0D%0Asecond line::100%25\n"
+        );
+
+        // Every violation stays on a single line even though its message spans several
+        let registry = process_files(&fixture_files(&["invalid/partial_protection.ts"]));
+        let annotations = to_github_annotations(&validate_registry(&registry), &[], git_root());
+        for line in annotations.lines() {
+            assert!(line.starts_with("::error file="));
+            assert!(line.contains("%0A"));
+        }
+    }
+}
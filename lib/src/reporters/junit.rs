@@ -0,0 +1,218 @@
+//! JUnit XML report with one test case per operation, failing when it has violations
+//!
+//! Findings that belong to a fragment instead of an operation (cycles, duplicate or unused
+//! fragments) get a test case for that fragment so they aren't lost. Documents that couldn't be
+//! analyzed get a test case per diagnostic, failing when the diagnostic is an error.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::registry::GraphQLRegistry;
+use crate::reporters::{escape_xml, relative_path};
+use crate::validate_registry::{ValidationError, ValidationResult};
+
+struct TestCase<'a> {
+    name: String,
+    failures: Vec<&'a ValidationError>,
+    warnings: Vec<&'a ValidationError>,
+}
+
+pub fn to_junit(
+    result: &ValidationResult,
+    registry: &GraphQLRegistry,
+    diagnostics: &[Diagnostic],
+    root: &Path,
+) -> String {
+    // Keyed by definition so test cases are ordered by file and line
+    let mut test_cases: BTreeMap<(PathBuf, u32, String), TestCase> = BTreeMap::new();
+    for entry in registry.queries.iter() {
+        for query in entry.value() {
+            // Anonymous operations of a file would otherwise share a name
+            let name = if query.anonymous {
+                format!("{} {} (line {})", query.kind, query.name, query.line)
+            } else {
+                format!("{} {}", query.kind, query.name)
+            };
+            test_cases.insert(
+                (query.file_path.clone(), query.line, query.kind.to_string()),
+                TestCase {
+                    name,
+                    failures: Vec::new(),
+                    warnings: Vec::new(),
+                },
+            );
+        }
+    }
+
+    for error in result.errors.iter().chain(&result.warnings) {
+        let context = &error.context;
+        let test_case = test_cases
+            .entry((
+                context.query_file.clone(),
+                context.query_line,
+                error.label(),
+            ))
+            .or_insert_with(|| TestCase {
+                name: format!("{} {}", error.label(), context.query_name),
+                failures: Vec::new(),
+                warnings: Vec::new(),
+            });
+        match error.severity {
+            Severity::Error => test_case.failures.push(error),
+            Severity::Warning | Severity::Info => test_case.warnings.push(error),
+        }
+    }
+
+    let tests = test_cases.len() + diagnostics.len();
+    let failures = test_cases
+        .values()
+        .filter(|test_case| !test_case.failures.is_empty())
+        .count()
+        + diagnostics.iter().filter(|d| d.is_error()).count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"gql-safeguard\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\">\n"
+    ));
+    xml.push_str(&format!(
+        "  <testsuite name=\"gql-safeguard\" tests=\"{tests}\" failures=\"{failures}\" errors=\"0\" skipped=\"0\">\n"
+    ));
+
+    for ((file_path, line, _), test_case) in &test_cases {
+        let file = escape_xml(&relative_path(file_path, root));
+        let attributes = format!(
+            "classname=\"{file}\" name=\"{}\" file=\"{file}\" line=\"{line}\"",
+            escape_xml(&test_case.name)
+        );
+        if test_case.failures.is_empty() && test_case.warnings.is_empty() {
+            xml.push_str(&format!("    <testcase {attributes}/>\n"));
+            continue;
+        }
+
+        xml.push_str(&format!("    <testcase {attributes}>\n"));
+        if !test_case.failures.is_empty() {
+            let mut codes: Vec<&str> = test_case
+                .failures
+                .iter()
+                .map(|error| error.error_type.code())
+                .collect();
+            codes.sort_unstable();
+            codes.dedup();
+            let message = match test_case.failures.as_slice() {
+                [error] => error.reason(),
+                errors => format!("{} violations", errors.len()),
+            };
+            xml.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                escape_xml(&message),
+                codes.join(","),
+                escape_xml(&details(&test_case.failures))
+            ));
+        }
+        // Warnings don't fail the test case but stay visible in the report
+        if !test_case.warnings.is_empty() {
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&details(&test_case.warnings))
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    for diagnostic in diagnostics {
+        let file = escape_xml(&relative_path(&diagnostic.file_path, root));
+        xml.push_str(&format!(
+            "    <testcase classname=\"{file}\" name=\"{}\" file=\"{file}\" line=\"{}\">\n",
+            escape_xml(&format!(
+                "{} at {}:{}",
+                diagnostic.label(),
+                diagnostic.line,
+                diagnostic.col
+            )),
+            diagnostic.line
+        ));
+        if diagnostic.is_error() {
+            xml.push_str(&format!(
+                "      <failure message=\"{}\" type=\"{}\">{}</failure>\n",
+                escape_xml(&diagnostic.message),
+                diagnostic.kind.code(),
+                escape_xml(&diagnostic.to_string())
+            ));
+        } else {
+            xml.push_str(&format!(
+                "      <system-out>{}</system-out>\n",
+                escape_xml(&diagnostic.to_string())
+            ));
+        }
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+// Same text as the terminal output, including the query tree
+fn details(errors: &[&ValidationError]) -> String {
+    errors
+        .iter()
+        .map(|error| error.to_string().trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::{promote_to_errors, DiagnosticKind, Phase};
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_junit_report() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/checkout_mutation.ts",
+            "edge_cases/circular_fragments.ts",
+            "valid/query_with_catch.tsx",
+            "valid/legacy_component.jsx",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        // Syntax errors fail their test case with `--fail-on-parse-error`
+        let mut diagnostics = registry.diagnostics.lock().unwrap().clone();
+        promote_to_errors(&mut diagnostics, DiagnosticKind::ParseError);
+        insta::assert_snapshot!(to_junit(
+            &validate_registry(&registry),
+            &registry,
+            &diagnostics,
+            git_root(),
+        ));
+    }
+
+    // Names and messages end up in attributes and text, the details contain the query tree
+    #[test]
+    fn test_junit_escapes_xml() {
+        let registry = process_files(&fixture_files(&["invalid/missing_catch.tsx"]));
+        let mut result = validate_registry(&registry);
+        result.errors[0].context.query_name = "Odd<&>\"'Query".to_string();
+        let diagnostic = Diagnostic {
+            file_path: git_root().join("src/a&b.ts"),
+            line: 3,
+            col: 4,
+            kind: DiagnosticKind::DynamicTemplate,
+            phase: Phase::Extraction,
+            severity: Severity::Error,
+            message: "template skipped: `${fields<T>}` can't be resolved statically".to_string(),
+        };
+
+        let xml = to_junit(&result, &registry, &[diagnostic], git_root());
+        assert!(xml.contains("Odd&lt;&amp;&gt;&quot;&apos;Query"));
+        assert!(xml.contains("classname=\"src/a&amp;b.ts\""));
+        assert!(xml.contains(
+            "message=\"template skipped: `${fields&lt;T&gt;}` can&apos;t be resolved statically\""
+        ));
+        assert!(!xml.contains("Odd<"));
+        assert!(!xml.contains("fields<T>"));
+    }
+}
@@ -0,0 +1,41 @@
+//! Machine-readable reports of a validation result for CI systems and code review tools
+
+use std::path::Path;
+
+use crate::validate_registry::ValidationError;
+
+pub mod checkstyle;
+pub mod codeclimate;
+pub mod compact;
+pub mod github;
+pub mod junit;
+pub mod sarif;
+
+// Path relative to `root` (the working directory of the run) with `/` separators, as expected
+// by report consumers
+pub(crate) fn relative_path(path: &Path, root: &Path) -> String {
+    let relative_path = path.strip_prefix(root).unwrap_or(path);
+    let relative_path = relative_path.strip_prefix(".").unwrap_or(relative_path);
+    relative_path.to_string_lossy().replace('\\', "/")
+}
+
+// Findings without a directive position point at the definition they belong to
+pub(crate) fn line(error: &ValidationError) -> u32 {
+    error.context.line.unwrap_or(error.context.query_line)
+}
+
+// Text and attribute values of the XML reports
+pub(crate) fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,236 @@
+//! SARIF 2.1.0 log for GitHub code scanning and other static analysis viewers
+//!
+//! Findings point at the offending directive. Traversal findings also link the operation and the
+//! fragment spreads leading to it, as related locations and as a code flow. Documents that couldn't
+//! be analyzed are reported as tool execution notifications.
+
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::reporters::relative_path;
+use crate::validate_registry::{ValidationError, ValidationErrorType, ValidationResult};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+// Conventional base id viewers resolve to the checkout of the repository
+const SRCROOT: &str = "%SRCROOT%";
+
+// Artifact URIs are relative to `root` (the working directory), exposed as `%SRCROOT%`
+pub fn to_sarif(result: &ValidationResult, diagnostics: &[Diagnostic], root: &Path) -> Value {
+    let rules: Vec<Value> = ValidationErrorType::ALL
+        .iter()
+        .map(|error_type| {
+            json!({
+                "id": error_type.code(),
+                "name": format!("{error_type:?}"),
+                "shortDescription": { "text": error_type.to_string() },
+                "defaultConfiguration": { "level": level(error_type.severity()) },
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = result
+        .errors
+        .iter()
+        .chain(&result.warnings)
+        .map(|error| sarif_result(error, root))
+        .collect();
+
+    let notifications: Vec<Value> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            json!({
+                "level": level(diagnostic.severity),
+                "message": { "text": format!("{}: {}", diagnostic.label(), diagnostic.message) },
+                "descriptor": { "id": diagnostic.kind.code() },
+                "locations": [{
+                    "physicalLocation": physical_location(
+                        &diagnostic.file_path,
+                        Some(diagnostic.line),
+                        Some(diagnostic.col),
+                        root,
+                    ),
+                }],
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "gql-safeguard",
+                    "informationUri": "https://github.com/jantimon/gql-safeguard",
+                    "rules": rules,
+                }
+            },
+            "invocations": [{
+                "executionSuccessful": !diagnostics.iter().any(Diagnostic::is_error),
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": results,
+        }]
+    })
+}
+
+fn sarif_result(error: &ValidationError, root: &Path) -> Value {
+    let rule_index = ValidationErrorType::ALL
+        .iter()
+        .position(|error_type| *error_type == error.error_type)
+        .unwrap_or_default();
+    let location = physical_location(
+        error.context.owner_file(),
+        error.context.line,
+        error.context.col,
+        root,
+    );
+
+    let mut result = json!({
+        "ruleId": error.error_type.code(),
+        "ruleIndex": rule_index,
+        "level": level(error.severity),
+        "message": { "text": error.reason() },
+        "locations": [{ "physicalLocation": location }],
+    });
+
+    // Registry-wide findings (duplicates, cycles, unused fragments) have no operation leading to them
+    if error.context.location_path.is_empty() {
+        return result;
+    }
+
+    let context = &error.context;
+    let mut steps = vec![(
+        physical_location(&context.query_file, Some(context.query_line), None, root),
+        format!("{} {}", error.label(), context.query_name),
+    )];
+    for spread in &context.spreads {
+        steps.push((
+            physical_location(&spread.file_path, Some(spread.line), Some(spread.col), root),
+            format!("...{}", spread.fragment_name),
+        ));
+    }
+
+    let related_locations: Vec<Value> = steps
+        .iter()
+        .enumerate()
+        .map(|(id, (location, text))| {
+            json!({
+                "id": id,
+                "physicalLocation": location,
+                "message": { "text": text },
+            })
+        })
+        .collect();
+
+    // Operation root, every spread on the way and finally the directive itself
+    let mut flow: Vec<Value> = steps
+        .into_iter()
+        .map(|(location, text)| {
+            json!({
+                "location": {
+                    "physicalLocation": location,
+                    "message": { "text": text },
+                }
+            })
+        })
+        .collect();
+    flow.push(json!({
+        "location": {
+            "physicalLocation": location,
+            "message": { "text": error.error_type.to_string() },
+        }
+    }));
+
+    result["relatedLocations"] = json!(related_locations);
+    result["codeFlows"] = json!([{ "threadFlows": [{ "locations": flow }] }]);
+    result
+}
+
+fn physical_location(file: &Path, line: Option<u32>, col: Option<u32>, root: &Path) -> Value {
+    let uri = relative_path(file, root);
+    // Files outside of the root can't be resolved against it
+    let artifact_location = if Path::new(&uri).is_absolute() {
+        json!({ "uri": format!("file://{uri}") })
+    } else {
+        json!({ "uri": uri, "uriBaseId": SRCROOT })
+    };
+    let mut location = json!({ "artifactLocation": artifact_location });
+    if let Some(line) = line {
+        let mut region = json!({ "startLine": line });
+        if let Some(col) = col {
+            region["startColumn"] = json!(col);
+        }
+        location["region"] = region;
+    }
+    location
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::process_files;
+    use crate::test_utils::{fixture_files, git_root};
+    use crate::validate_registry::validate_registry;
+
+    #[test]
+    fn test_sarif_report() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/unprotected_nested.ts",
+            "invalid/partial_protection.ts",
+            "edge_cases/unknown_fragments.ts",
+            "valid/legacy_component.jsx",
+            "edge_cases/graphql_syntax_error.ts",
+            "edge_cases/typescript_syntax_error.ts",
+        ]));
+        let sarif = to_sarif(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        );
+        insta::assert_snapshot!(serde_json::to_string_pretty(&sarif).unwrap());
+    }
+
+    // Results reference their rule by index and every location resolves against `%SRCROOT%`
+    #[test]
+    fn test_sarif_rule_indices_and_base_ids() {
+        let registry = process_files(&fixture_files(&[
+            "invalid/partial_protection.ts",
+            "edge_cases/circular_fragments.ts",
+            "edge_cases/graphql_syntax_error.ts",
+        ]));
+        let sarif = to_sarif(
+            &validate_registry(&registry),
+            &registry.diagnostics.lock().unwrap(),
+            git_root(),
+        );
+        let run = &sarif["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+
+        let results = run["results"].as_array().unwrap();
+        assert!(!results.is_empty());
+        for result in results {
+            let rule = &rules[result["ruleIndex"].as_u64().unwrap() as usize];
+            assert_eq!(rule["id"], result["ruleId"]);
+            let artifact = &result["locations"][0]["physicalLocation"]["artifactLocation"];
+            assert_eq!(artifact["uriBaseId"], SRCROOT);
+            assert!(artifact["uri"].as_str().unwrap().starts_with("fixtures/"));
+        }
+
+        let invocation = &run["invocations"][0];
+        assert_eq!(invocation["executionSuccessful"], true);
+        assert_eq!(
+            invocation["toolExecutionNotifications"][0]["descriptor"]["id"],
+            "parseError"
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Rule 4: spread fragments must be part of the registry
+//!
+//! Checked for every spread of every definition, protected or not: a typo under `@catch` or in
+//! a fragment no operation uses yet is still a broken document.
+
+use crate::parsers::graphql_parser::FragmentSpread;
+use crate::registry::GraphQLRegistry;
+use crate::rules::{Rule, RuleContext};
+use crate::validate_registry::ValidationErrorType;
+
+pub struct KnownFragments;
+
+impl Rule for KnownFragments {
+    fn error_types(&self) -> &'static [ValidationErrorType] {
+        &[ValidationErrorType::UnknownFragment]
+    }
+
+    // One error per spread of a name that isn't defined anywhere, owned by the definition
+    // the spread is written in
+    fn spread(&self, spread: &FragmentSpread, ctx: &mut RuleContext) {
+        let registry = ctx.registry();
+        if registry.fragments.contains_key(&spread.name) {
+            return;
+        }
+
+        let explanation = similar_fragment_name(registry, &spread.name)
+            .map(|suggestion| format!("did you mean {suggestion}?"))
+            .unwrap_or_default();
+        ctx.report(
+            ValidationErrorType::UnknownFragment,
+            Some(spread.name.clone()),
+            Some(spread.line),
+            Some(spread.col),
+            explanation,
+        );
+    }
+}
+
+// Closest registered fragment name for typos like `...UserFeilds`
+fn similar_fragment_name(registry: &GraphQLRegistry, name: &str) -> Option<String> {
+    // Allow roughly one edit per three characters so short names don't match everything
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    registry
+        .fragments
+        .iter()
+        .map(|entry| (strsim::levenshtein(name, entry.key()), entry.key().clone()))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
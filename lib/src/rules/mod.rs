@@ -0,0 +1,386 @@
+//! Validation rules driven by a single traversal of every operation
+//!
+//! The traversal in `validate_registry` tracks @catch protection and the fragment being
+//! walked, and hands every field, fragment, inline fragment and directive to each enabled
+//! rule. Fragment definitions are walked on their own for the spreads they contain. Rules
+//! that need the registry as a whole (names, cycles, unused fragments) run once after the
+//! traversal.
+
+pub mod known_fragments;
+pub mod no_fragment_cycles;
+pub mod protection_requirement;
+pub mod unique_names;
+pub mod unused_fragments;
+
+use globset::GlobSet;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::Severity;
+use crate::parsers::graphql_parser::{
+    Directive, FieldSelection, FragmentDefinition, FragmentSpread, InlineFragment, OperationKind,
+    QueryOperation,
+};
+use crate::registry::GraphQLRegistry;
+use crate::validate_registry::{
+    create_optimized_tree_visualization, ErrorContext, SpreadLocation, ValidationError,
+    ValidationErrorType,
+};
+use known_fragments::KnownFragments;
+use no_fragment_cycles::NoFragmentCycles;
+use protection_requirement::ProtectionRequirement;
+use unique_names::UniqueNames;
+use unused_fragments::UnusedFragments;
+
+// Protection state for efficient subtree skipping
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProtectionState {
+    Protected,   // @catch found, skip all nested validation
+    Unprotected, // No @catch protection, must validate all directives
+}
+
+// Node a directive is attached to
+#[derive(Debug, Clone, Copy)]
+pub enum DirectiveOwner<'a> {
+    Operation,
+    Field(&'a FieldSelection),
+    FragmentSpread(&'a FragmentSpread),
+    InlineFragment(&'a InlineFragment),
+}
+
+// Definition a traversal starts at
+#[derive(Debug, Clone, Copy)]
+pub enum Definition<'a> {
+    Operation(&'a QueryOperation),
+    Fragment(&'a FragmentDefinition),
+}
+
+impl<'a> Definition<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            Definition::Operation(query) => &query.name,
+            Definition::Fragment(fragment) => &fragment.name,
+        }
+    }
+
+    pub fn file_path(&self) -> &'a PathBuf {
+        match self {
+            Definition::Operation(query) => &query.file_path,
+            Definition::Fragment(fragment) => &fragment.file_path,
+        }
+    }
+
+    pub fn line(&self) -> u32 {
+        match self {
+            Definition::Operation(query) => query.line,
+            Definition::Fragment(fragment) => fragment.line,
+        }
+    }
+
+    // None for fragments, findings in them are owned by the fragment
+    pub fn operation_kind(&self) -> Option<OperationKind> {
+        match self {
+            Definition::Operation(query) => Some(query.kind),
+            Definition::Fragment(_) => None,
+        }
+    }
+}
+
+// Traversal state of one operation or fragment definition shared by all rules
+pub struct RuleContext<'a> {
+    pub(crate) registry: &'a GraphQLRegistry,
+    pub(crate) definition: Definition<'a>,
+    pub(crate) protection_state: ProtectionState,
+    // Path of the current node like `query.user...UserFields.name`
+    pub(crate) location: String,
+    pub(crate) current_fragment_file: Option<PathBuf>,
+    pub(crate) current_fragment_name: Option<String>,
+    pub(crate) spreads: Vec<SpreadLocation>,
+    pub(crate) errors: Vec<ValidationError>,
+}
+
+impl<'a> RuleContext<'a> {
+    pub fn registry(&self) -> &'a GraphQLRegistry {
+        self.registry
+    }
+
+    pub fn definition(&self) -> Definition<'a> {
+        self.definition
+    }
+
+    // None while a fragment definition is walked on its own
+    pub fn operation(&self) -> Option<&'a QueryOperation> {
+        match self.definition {
+            Definition::Operation(query) => Some(query),
+            Definition::Fragment(_) => None,
+        }
+    }
+
+    // True below a @catch - content of protected fragment spreads is skipped entirely
+    pub fn is_protected(&self) -> bool {
+        self.protection_state == ProtectionState::Protected
+    }
+
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    // Fragment whose selections are being walked, None directly inside the operation
+    pub fn fragment_name(&self) -> Option<&str> {
+        self.current_fragment_name.as_deref()
+    }
+
+    pub fn fragment_file(&self) -> Option<&PathBuf> {
+        self.current_fragment_file.as_ref()
+    }
+
+    // Spreads leading from the operation to the current node
+    pub fn spreads(&self) -> &[SpreadLocation] {
+        &self.spreads
+    }
+
+    // Reports a finding at the current location, with the operation tree highlighting it
+    pub fn report(
+        &mut self,
+        error_type: ValidationErrorType,
+        fragment_name: Option<String>,
+        line: Option<u32>,
+        col: Option<u32>,
+        explanation: String,
+    ) {
+        let tree_visualization = self
+            .operation()
+            .map(|query| {
+                create_optimized_tree_visualization(self.registry, query, Some(&self.location))
+            })
+            .unwrap_or_default();
+        let owner_file = self
+            .current_fragment_file
+            .as_ref()
+            .unwrap_or(self.definition.file_path());
+        let code_frame = line
+            .map(|line| self.registry.code_frame(owner_file, line, col))
+            .unwrap_or_default();
+        self.errors.push(ValidationError {
+            severity: error_type.severity(),
+            error_type,
+            context: ErrorContext {
+                query_name: self.definition.name().to_string(),
+                operation_kind: self.definition.operation_kind(),
+                query_file: self.definition.file_path().clone(),
+                query_line: self.definition.line(),
+                location_path: self.location.clone(),
+                fragment_file: self.current_fragment_file.clone(),
+                fragment_name,
+                line,
+                col,
+                spreads: self.spreads.clone(),
+            },
+            tree_visualization,
+            code_frame,
+            explanation,
+        });
+    }
+}
+
+// A check run during validation, every hook defaults to doing nothing
+//
+// Operations are validated in parallel, so rules keep no state of their own and report
+// through the `RuleContext`.
+pub trait Rule: Send + Sync {
+    // Findings this rule reports, a rule only runs while at least one of them is enabled
+    fn error_types(&self) -> &'static [ValidationErrorType];
+
+    fn enter_operation(&self, _operation: &QueryOperation, _ctx: &mut RuleContext) {}
+
+    fn leave_operation(&self, _operation: &QueryOperation, _ctx: &mut RuleContext) {}
+
+    fn enter_field(&self, _field: &FieldSelection, _ctx: &mut RuleContext) {}
+
+    fn leave_field(&self, _field: &FieldSelection, _ctx: &mut RuleContext) {}
+
+    // Every spread written in the walked operation or fragment definition, protected or not
+    // Spreads inside a fragment are visited once, when the fragment definition is walked
+    fn spread(&self, _spread: &FragmentSpread, _ctx: &mut RuleContext) {}
+
+    // Spread in an unprotected subtree whose content is about to be validated
+    // `fragment` is None if the registry doesn't know the spread fragment
+    fn enter_fragment_spread(
+        &self,
+        _spread: &FragmentSpread,
+        _fragment: Option<&FragmentDefinition>,
+        _ctx: &mut RuleContext,
+    ) {
+    }
+
+    fn leave_fragment_spread(&self, _spread: &FragmentSpread, _ctx: &mut RuleContext) {}
+
+    fn enter_inline_fragment(&self, _inline: &InlineFragment, _ctx: &mut RuleContext) {}
+
+    fn leave_inline_fragment(&self, _inline: &InlineFragment, _ctx: &mut RuleContext) {}
+
+    // Every @catch, @throwOnFieldError and @required(action: THROW) of the traversed nodes
+    fn directive(&self, _directive: &Directive, _owner: DirectiveOwner, _ctx: &mut RuleContext) {}
+
+    // Checks of the registry as a whole, run once after all operations were traversed
+    fn check_registry(&self, _registry: &GraphQLRegistry) -> Vec<ValidationError> {
+        Vec::new()
+    }
+}
+
+// Definition location listed in explanations, relative to the git root like the query tree
+pub(crate) fn display_path(path: &Path) -> std::path::Display<'_> {
+    let git_root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    path.strip_prefix(git_root).unwrap_or(path).display()
+}
+
+// Rules used for validation, the findings that are turned off and changed severities
+pub struct RuleSet {
+    rules: Vec<Box<dyn Rule>>,
+    disabled: Vec<ValidationErrorType>,
+    severities: Vec<(ValidationErrorType, Severity)>,
+    // Applied in order on top of the levels above, later overrides win
+    overrides: Vec<RuleOverride>,
+}
+
+// Levels for findings in files matching the globs, e.g. warnings only in `apps/legacy/**`
+pub struct RuleOverride {
+    // Globs are matched relative to this directory (the one of the config file)
+    root: PathBuf,
+    files: GlobSet,
+    disabled: Vec<ValidationErrorType>,
+    severities: Vec<(ValidationErrorType, Severity)>,
+}
+
+impl RuleOverride {
+    pub fn new(root: PathBuf, files: GlobSet) -> Self {
+        Self {
+            root,
+            files,
+            disabled: Vec::new(),
+            severities: Vec::new(),
+        }
+    }
+
+    pub fn disable(&mut self, error_type: ValidationErrorType) {
+        self.severities
+            .retain(|(existing, _)| *existing != error_type);
+        self.disabled.push(error_type);
+    }
+
+    // Also enables findings turned off outside of the override
+    pub fn set_severity(&mut self, error_type: ValidationErrorType, severity: Severity) {
+        self.disabled.retain(|existing| *existing != error_type);
+        self.severities
+            .retain(|(existing, _)| *existing != error_type);
+        self.severities.push((error_type, severity));
+    }
+
+    pub fn matches(&self, file: &Path) -> bool {
+        // Canonical like the root and the paths the walker matches include/exclude against,
+        // paths outside of the root (or not resolvable) are matched as they are
+        let file = file
+            .canonicalize()
+            .or_else(|_| std::path::absolute(file))
+            .unwrap_or_else(|_| file.to_path_buf());
+        let relative = file.strip_prefix(&self.root).unwrap_or(&file);
+        self.files.is_match(relative)
+    }
+}
+
+impl Default for RuleSet {
+    // All built-in rules
+    fn default() -> Self {
+        Self::empty()
+            .with_rule(ProtectionRequirement)
+            .with_rule(KnownFragments)
+            .with_rule(UniqueNames)
+            .with_rule(NoFragmentCycles)
+            .with_rule(UnusedFragments)
+    }
+}
+
+impl RuleSet {
+    pub fn empty() -> Self {
+        Self {
+            rules: Vec::new(),
+            disabled: Vec::new(),
+            severities: Vec::new(),
+            overrides: Vec::new(),
+        }
+    }
+
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    // Drops every finding of this type, rules without other enabled findings are skipped
+    pub fn disable(&mut self, error_type: ValidationErrorType) {
+        if !self.disabled.contains(&error_type) {
+            self.disabled.push(error_type);
+        }
+    }
+
+    pub fn enable(&mut self, error_type: &ValidationErrorType) {
+        self.disabled.retain(|disabled| disabled != error_type);
+    }
+
+    pub fn is_enabled(&self, error_type: &ValidationErrorType) -> bool {
+        !self.disabled.contains(error_type)
+    }
+
+    // Reports the finding as error or warning regardless of its default severity
+    pub fn set_severity(&mut self, error_type: ValidationErrorType, severity: Severity) {
+        self.severities
+            .retain(|(existing, _)| *existing != error_type);
+        self.severities.push((error_type, severity));
+    }
+
+    pub fn severity(&self, error_type: &ValidationErrorType) -> Severity {
+        self.severities
+            .iter()
+            .find(|(existing, _)| existing == error_type)
+            .map_or_else(|| error_type.severity(), |(_, severity)| *severity)
+    }
+
+    pub fn add_override(&mut self, rule_override: RuleOverride) {
+        self.overrides.push(rule_override);
+    }
+
+    // Severity of a finding owned by `file`, None if it is turned off there
+    pub fn level(&self, error_type: &ValidationErrorType, file: &Path) -> Option<Severity> {
+        let mut level = self
+            .is_enabled(error_type)
+            .then(|| self.severity(error_type));
+        for rule_override in self.overrides.iter().filter(|o| o.matches(file)) {
+            if rule_override.disabled.contains(error_type) {
+                level = None;
+            } else if let Some((_, severity)) = rule_override
+                .severities
+                .iter()
+                .find(|(existing, _)| existing == error_type)
+            {
+                level = Some(*severity);
+            }
+        }
+        level
+    }
+
+    // Rules reporting at least one finding that is enabled globally or in an override
+    pub fn active_rules(&self) -> Vec<&dyn Rule> {
+        let is_active = |error_type: &ValidationErrorType| {
+            self.is_enabled(error_type)
+                || self.overrides.iter().any(|rule_override| {
+                    rule_override
+                        .severities
+                        .iter()
+                        .any(|(existing, _)| existing == error_type)
+                })
+        };
+        self.rules
+            .iter()
+            .filter(|rule| rule.error_types().iter().any(is_active))
+            .map(|rule| rule.as_ref())
+            .collect()
+    }
+}
@@ -0,0 +1,64 @@
+//! Rule 5: fragments must not spread each other in a loop
+
+use crate::fragment_cycles::find_fragment_cycles;
+use crate::registry::GraphQLRegistry;
+use crate::rules::{display_path, Rule};
+use crate::validate_registry::{ErrorContext, ValidationError, ValidationErrorType};
+
+pub struct NoFragmentCycles;
+
+impl Rule for NoFragmentCycles {
+    fn error_types(&self) -> &'static [ValidationErrorType] {
+        &[ValidationErrorType::FragmentCycle]
+    }
+
+    // One error per group of fragments spreading each other, showing the chain and every spread in it
+    fn check_registry(&self, registry: &GraphQLRegistry) -> Vec<ValidationError> {
+        find_fragment_cycles(registry)
+            .into_iter()
+            .map(|cycle| {
+                let mut explanation = cycle.chain();
+                for (i, step) in cycle.steps.iter().enumerate() {
+                    let next = &cycle.steps[(i + 1) % cycle.steps.len()].fragment;
+                    explanation.push_str(&format!(
+                        "\n  - {} spreads ...{next} at {}:{}:{}",
+                        step.fragment,
+                        display_path(&step.file_path),
+                        step.line,
+                        step.col
+                    ));
+                }
+                if !cycle.other_fragments.is_empty() {
+                    explanation.push_str(&format!(
+                        "\n  also part of the cycle: {}",
+                        cycle.other_fragments.join(", ")
+                    ));
+                }
+
+                let first = &cycle.steps[0];
+                let definition_line = registry
+                    .fragment(&first.fragment)
+                    .map_or(first.line, |fragment| fragment.line);
+                ValidationError {
+                    error_type: ValidationErrorType::FragmentCycle,
+                    severity: ValidationErrorType::FragmentCycle.severity(),
+                    context: ErrorContext {
+                        query_name: first.fragment.clone(),
+                        operation_kind: None,
+                        query_file: first.file_path.clone(),
+                        query_line: definition_line,
+                        location_path: String::new(),
+                        fragment_file: None,
+                        fragment_name: None,
+                        line: Some(first.line),
+                        col: Some(first.col),
+                        spreads: Vec::new(),
+                    },
+                    tree_visualization: String::new(),
+                    code_frame: registry.code_frame(&first.file_path, first.line, Some(first.col)),
+                    explanation,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,41 @@
+//! Rule 1: every throwing directive needs a @catch above it
+
+use crate::parsers::graphql_parser::{Directive, DirectiveType};
+use crate::rules::{DirectiveOwner, Rule, RuleContext};
+use crate::validate_registry::ValidationErrorType;
+
+pub struct ProtectionRequirement;
+
+impl Rule for ProtectionRequirement {
+    fn error_types(&self) -> &'static [ValidationErrorType] {
+        &[
+            ValidationErrorType::UnprotectedThrowOnFieldError,
+            ValidationErrorType::UnprotectedRequiredThrow,
+        ]
+    }
+
+    fn directive(&self, directive: &Directive, owner: DirectiveOwner, ctx: &mut RuleContext) {
+        if directive.directive_type == DirectiveType::Catch || ctx.is_protected() {
+            return;
+        }
+
+        let fragment_name = match owner {
+            DirectiveOwner::Operation => None,
+            DirectiveOwner::Field(_) => ctx.fragment_name().map(str::to_string),
+            DirectiveOwner::FragmentSpread(spread) => Some(spread.name.clone()),
+            DirectiveOwner::InlineFragment(inline) => inline
+                .type_condition
+                .as_ref()
+                .and_then(|tc| tc.strip_suffix("Fragment"))
+                .map(|name| name.to_string()),
+        };
+
+        ctx.report(
+            ValidationErrorType::unprotected(&directive.directive_type),
+            fragment_name,
+            Some(directive.line),
+            Some(directive.col),
+            String::new(),
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Rule 3: operation and fragment names must be unique
+
+use std::path::PathBuf;
+
+use crate::registry::GraphQLRegistry;
+use crate::rules::{display_path, Rule};
+use crate::validate_registry::{ErrorContext, ValidationError, ValidationErrorType};
+
+pub struct UniqueNames;
+
+impl Rule for UniqueNames {
+    fn error_types(&self) -> &'static [ValidationErrorType] {
+        &[
+            ValidationErrorType::DuplicateOperationName,
+            ValidationErrorType::DuplicateFragmentName,
+        ]
+    }
+
+    // One error per name defined more than once, listing every definition
+    fn check_registry(&self, registry: &GraphQLRegistry) -> Vec<ValidationError> {
+        let list_locations = |name: &str, locations: Vec<(&PathBuf, u32)>| {
+            let mut explanation = format!("{name} is defined {} times:", locations.len());
+            for (file_path, line) in locations {
+                explanation.push_str(&format!("\n  - {}:{line}", display_path(file_path)));
+            }
+            explanation
+        };
+
+        let mut errors = Vec::new();
+
+        for entry in registry.queries.iter() {
+            let definitions = entry.value();
+            if definitions.len() < 2 {
+                continue;
+            }
+            let first = &definitions[0];
+            errors.push(ValidationError {
+                error_type: ValidationErrorType::DuplicateOperationName,
+                severity: ValidationErrorType::DuplicateOperationName.severity(),
+                context: ErrorContext {
+                    query_name: first.name.clone(),
+                    operation_kind: Some(first.kind),
+                    query_file: first.file_path.clone(),
+                    query_line: first.line,
+                    location_path: String::new(),
+                    fragment_file: None,
+                    fragment_name: None,
+                    line: Some(first.line),
+                    col: None,
+                    spreads: Vec::new(),
+                },
+                tree_visualization: String::new(),
+                code_frame: registry.code_frame(&first.file_path, first.line, None),
+                explanation: list_locations(
+                    &first.name,
+                    definitions.iter().map(|d| (&d.file_path, d.line)).collect(),
+                ),
+            });
+        }
+
+        for entry in registry.fragments.iter() {
+            let definitions = entry.value();
+            if definitions.len() < 2 {
+                continue;
+            }
+            let first = &definitions[0];
+            errors.push(ValidationError {
+                error_type: ValidationErrorType::DuplicateFragmentName,
+                severity: ValidationErrorType::DuplicateFragmentName.severity(),
+                context: ErrorContext {
+                    query_name: first.name.clone(),
+                    operation_kind: None,
+                    query_file: first.file_path.clone(),
+                    query_line: first.line,
+                    location_path: String::new(),
+                    fragment_file: None,
+                    fragment_name: None,
+                    line: Some(first.line),
+                    col: None,
+                    spreads: Vec::new(),
+                },
+                tree_visualization: String::new(),
+                code_frame: registry.code_frame(&first.file_path, first.line, None),
+                explanation: list_locations(
+                    &first.name,
+                    definitions.iter().map(|d| (&d.file_path, d.line)).collect(),
+                ),
+            });
+        }
+
+        // DashMap iteration order is arbitrary
+        errors.sort_by(|a, b| a.context.query_name.cmp(&b.context.query_name));
+        errors
+    }
+}
@@ -0,0 +1,151 @@
+//! Rule 6: fragments must be reachable from an operation, unused ones are never validated
+
+use rustc_hash::FxHashSet;
+
+use crate::parsers::graphql_parser::{Directive, DirectiveType, Selection};
+use crate::registry::GraphQLRegistry;
+use crate::rules::Rule;
+use crate::validate_registry::{ErrorContext, ValidationError, ValidationErrorType};
+
+pub struct UnusedFragments;
+
+impl Rule for UnusedFragments {
+    fn error_types(&self) -> &'static [ValidationErrorType] {
+        &[
+            ValidationErrorType::UnusedFragment,
+            ValidationErrorType::UnreachableThrow,
+        ]
+    }
+
+    // Fragments no operation reaches through the spread index, with the first unprotected throwing
+    // directive they contain pointed out since it is never validated
+    fn check_registry(&self, registry: &GraphQLRegistry) -> Vec<ValidationError> {
+        let mut used: FxHashSet<String> = FxHashSet::default();
+        let mut pending: Vec<String> = registry
+            .queries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .value()
+                    .iter()
+                    .flat_map(|query| query.fragments())
+                    .map(|spread| spread.name)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        while let Some(name) = pending.pop() {
+            if !used.insert(name.clone()) {
+                continue;
+            }
+            if let Some(entry) = registry.fragments.get(&name) {
+                for fragment in entry.value() {
+                    pending.extend(fragment.fragments().into_iter().map(|spread| spread.name));
+                }
+            }
+        }
+
+        let mut warnings: Vec<ValidationError> = registry
+            .fragments
+            .iter()
+            .filter(|entry| !used.contains(entry.key()))
+            .flat_map(|entry| entry.value().clone())
+            .map(|fragment| {
+                // A fragment-level @catch protects all content once the fragment gets used
+                let fragment_has_catch = fragment
+                    .directives
+                    .iter()
+                    .any(|d| d.directive_type == DirectiveType::Catch);
+                let throwing_directive = fragment
+                    .directives
+                    .iter()
+                    .find(|d| d.directive_type != DirectiveType::Catch)
+                    .or_else(|| {
+                        (!fragment_has_catch)
+                            .then(|| first_unprotected_throw(&fragment.selections))
+                            .flatten()
+                    })
+                    .cloned();
+
+                let (error_type, line, col, explanation) = match throwing_directive {
+                    Some(directive) => (
+                        ValidationErrorType::UnreachableThrow,
+                        directive.line,
+                        Some(directive.col),
+                        format!(
+                            "@{} is never checked because {} isn't spread by any operation",
+                            directive.directive_type, fragment.name
+                        ),
+                    ),
+                    None => (
+                        ValidationErrorType::UnusedFragment,
+                        fragment.line,
+                        None,
+                        format!("{} isn't spread by any operation", fragment.name),
+                    ),
+                };
+
+                ValidationError {
+                    severity: error_type.severity(),
+                    error_type,
+                    context: ErrorContext {
+                        query_name: fragment.name.clone(),
+                        operation_kind: None,
+                        query_file: fragment.file_path.clone(),
+                        query_line: fragment.line,
+                        location_path: String::new(),
+                        fragment_file: None,
+                        fragment_name: None,
+                        line: Some(line),
+                        col,
+                        spreads: Vec::new(),
+                    },
+                    tree_visualization: String::new(),
+                    code_frame: registry.code_frame(&fragment.file_path, line, col),
+                    explanation,
+                }
+            })
+            .collect();
+
+        // DashMap iteration order is arbitrary
+        warnings.sort_by(|a, b| {
+            (&a.context.query_name, &a.context.query_file, a.context.line).cmp(&(
+                &b.context.query_name,
+                &b.context.query_file,
+                b.context.line,
+            ))
+        });
+        warnings
+    }
+}
+
+// First @throwOnFieldError / @required(action: THROW) that no @catch within the selections protects
+fn first_unprotected_throw(selections: &[Selection]) -> Option<&Directive> {
+    let is_throwing = |d: &&Directive| d.directive_type != DirectiveType::Catch;
+    let has_catch = |directives: &[Directive]| {
+        directives
+            .iter()
+            .any(|d| d.directive_type == DirectiveType::Catch)
+    };
+
+    selections.iter().find_map(|selection| match selection {
+        // Like during validation a field's own directives depend on its parent's protection
+        Selection::Field(field) => field.directives.iter().find(is_throwing).or_else(|| {
+            if has_catch(&field.directives) {
+                None
+            } else {
+                first_unprotected_throw(&field.selections)
+            }
+        }),
+        Selection::FragmentSpread(spread) if has_catch(&spread.directives) => None,
+        Selection::FragmentSpread(spread) => spread.directives.iter().find(is_throwing),
+        Selection::InlineFragment(inline) => {
+            inline.directives.iter().find(is_throwing).or_else(|| {
+                if has_catch(&inline.directives) {
+                    None
+                } else {
+                    first_unprotected_throw(&inline.selections)
+                }
+            })
+        }
+    })
+}
@@ -0,0 +1,20 @@
+//! Fixture helpers shared by the unit tests
+
+use std::path::{Path, PathBuf};
+
+// Repository root, snapshots show paths relative to it
+pub(crate) fn git_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap()
+}
+
+pub(crate) fn fixture_path(relative: &str) -> PathBuf {
+    git_root().join("fixtures").join(relative)
+}
+
+// Fixture paths in the form `process_files` takes them
+pub(crate) fn fixture_files(relative: &[&str]) -> Vec<String> {
+    relative
+        .iter()
+        .map(|relative| fixture_path(relative).to_string_lossy().to_string())
+        .collect()
+}
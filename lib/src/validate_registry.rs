@@ -3,6 +3,8 @@
 //! Validates @throwOnFieldError directives have proper @catch protection by working
 //! directly with the registry instead of expanding all fragments. Provides significant
 //! performance improvements through subtree skipping and parallel processing.
+//!
+//! The checks themselves are `Rule`s (see `rules`) fed by the traversal below.
 
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
@@ -10,22 +12,24 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Mutex;
 
-use crate::parsers::graphql_parser::{DirectiveType, Selection};
+use crate::baseline::BaselineEntry;
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::parsers::graphql_parser::{
+    Directive, DirectiveType, FragmentSpread, OperationKind, QueryOperation, Selection,
+};
 use crate::registry::GraphQLRegistry;
+use crate::rules::{Definition, DirectiveOwner, ProtectionState, Rule, RuleContext, RuleSet};
 use crate::tree_formatter::TreeFormatter;
 
-// Validation error types
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum ValidationErrorType {
-    UnprotectedThrowOnFieldError,
-}
+pub use crate::codes::ValidationErrorType;
 
-impl std::fmt::Display for ValidationErrorType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ValidationErrorType::UnprotectedThrowOnFieldError => {
-                write!(f, "Unprotected @throwOnFieldError")
-            }
+// Depends on the parsed directive, so it lives next to the traversal instead of `codes`
+impl ValidationErrorType {
+    // Error reported for an unprotected throwing directive
+    pub fn unprotected(directive_type: &DirectiveType) -> Self {
+        match directive_type {
+            DirectiveType::RequiredThrow => ValidationErrorType::UnprotectedRequiredThrow,
+            _ => ValidationErrorType::UnprotectedThrowOnFieldError,
         }
     }
 }
@@ -33,25 +37,54 @@ impl std::fmt::Display for ValidationErrorType {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ErrorContext {
     pub query_name: String,
+    // Kind of the operation owning the finding, None for findings owned by a fragment
+    pub operation_kind: Option<OperationKind>,
     pub query_file: PathBuf,
+    // Line of the operation definition, tells anonymous operations of a file apart
+    pub query_line: u32,
     pub location_path: String,
     pub fragment_file: Option<PathBuf>,
     pub fragment_name: Option<String>,
     pub line: Option<u32>,
     pub col: Option<u32>,
+    // Spreads leading from the operation to the finding, outermost first
+    pub spreads: Vec<SpreadLocation>,
+}
+
+// Fragment spread on the way from an operation to a finding
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpreadLocation {
+    pub fragment_name: String,
+    // Operation or fragment file containing the spread
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl ErrorContext {
+    // File containing the reported directive, spread or definition
+    pub fn owner_file(&self) -> &PathBuf {
+        self.fragment_file.as_ref().unwrap_or(&self.query_file)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidationError {
     pub error_type: ValidationErrorType,
+    // Defaults to the severity of the error type, the `RuleSet` may change it
+    pub severity: Severity,
     pub context: ErrorContext,
     pub tree_visualization: String,
+    // Source lines around the finding, rendered while the registry is at hand
+    pub code_frame: String,
     pub explanation: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ValidationResult {
     pub errors: Vec<ValidationError>,
+    // Findings with `Severity::Warning` that don't fail validation
+    pub warnings: Vec<ValidationError>,
 }
 
 impl Default for ValidationResult {
@@ -62,11 +95,17 @@ impl Default for ValidationResult {
 
 impl ValidationResult {
     pub fn new() -> Self {
-        Self { errors: Vec::new() }
+        Self {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
     }
 
     pub fn add_error(&mut self, error: ValidationError) {
-        self.errors.push(error);
+        match error.severity {
+            Severity::Error => self.errors.push(error),
+            Severity::Warning | Severity::Info => self.warnings.push(error),
+        }
     }
 
     pub fn has_errors(&self) -> bool {
@@ -81,6 +120,8 @@ impl ValidationResult {
 // JSON-serializable error types for programmatic consumption
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonValidationError {
+    // Rule code like `GQS001`
+    pub code: String,
     #[serde(rename = "fileName")]
     pub file_name: String,
     pub reason: String,
@@ -96,6 +137,68 @@ pub struct JsonValidationError {
 pub struct JsonValidationResult {
     pub errors: Vec<JsonValidationError>,
     pub hint: String,
+    // Findings that don't fail validation like unused fragments
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<JsonValidationError>,
+    // Extraction findings like skipped dynamic templates
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<Diagnostic>,
+    // Baseline entries that no longer occur and can be removed
+    #[serde(
+        rename = "fixedBaselineEntries",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub fixed_baseline_entries: Vec<BaselineEntry>,
+}
+
+impl ValidationError {
+    // One-line description of the finding used by the JSON and annotation outputs
+    pub fn reason(&self) -> String {
+        match self.error_type {
+            ValidationErrorType::UnprotectedThrowOnFieldError => {
+                "@throwOnFieldError must not be used without @catch".to_string()
+            }
+            ValidationErrorType::UnprotectedRequiredThrow => {
+                "@requiredThrow must not be used without @catch".to_string()
+            }
+            ValidationErrorType::DuplicateOperationName => {
+                format!("operation name {} must be unique", self.context.query_name)
+            }
+            ValidationErrorType::DuplicateFragmentName => {
+                format!("fragment name {} must be unique", self.context.query_name)
+            }
+            ValidationErrorType::UnknownFragment => {
+                let name = self.context.fragment_name.as_deref().unwrap_or_default();
+                if self.explanation.is_empty() {
+                    format!("fragment {name} is not defined")
+                } else {
+                    format!("fragment {name} is not defined, {}", self.explanation)
+                }
+            }
+            ValidationErrorType::FragmentCycle => {
+                // First line of the explanation is the chain `A -> B -> A`
+                let chain = self.explanation.lines().next().unwrap_or_default();
+                format!("fragments must not spread each other in a cycle: {chain}")
+            }
+            ValidationErrorType::UnusedFragment => format!(
+                "fragment {} is not used by any operation",
+                self.context.query_name
+            ),
+            ValidationErrorType::UnreachableThrow => format!(
+                "fragment {} is not used by any operation, its throwing directives are never checked",
+                self.context.query_name
+            ),
+        }
+    }
+
+    // What `query_name` names, e.g. `query` or `fragment`
+    pub fn label(&self) -> String {
+        match self.context.operation_kind {
+            Some(kind) => kind.to_string(),
+            None => "fragment".to_string(),
+        }
+    }
 }
 
 impl From<ValidationError> for JsonValidationError {
@@ -109,17 +212,7 @@ impl From<ValidationError> for JsonValidationError {
             .unwrap_or("")
             .to_string();
 
-        // Generate appropriate reason based on error type
-        let reason = match error.error_type {
-            ValidationErrorType::UnprotectedThrowOnFieldError => {
-                // Check if this came from a RequiredThrow directive by examining the tree
-                if error.tree_visualization.contains("@requiredThrow") {
-                    "@requiredThrow must not be used without @catch".to_string()
-                } else {
-                    "@throwOnFieldError must not be used without @catch".to_string()
-                }
-            }
-        };
+        let reason = error.reason();
 
         // Use relative path by stripping git root (same logic as Display impl)
         let git_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -134,6 +227,7 @@ impl From<ValidationError> for JsonValidationError {
             .unwrap_or(&error.context.query_file);
 
         JsonValidationError {
+            code: error.error_type.code().to_string(),
             file_name: relative_path.display().to_string(),
             reason,
             name: error
@@ -150,7 +244,14 @@ impl From<ValidationError> for JsonValidationError {
 
 impl From<ValidationResult> for JsonValidationResult {
     fn from(result: ValidationResult) -> Self {
-        let hint = if result.is_valid() {
+        let has_unprotected_directives = result.errors.iter().any(|error| {
+            matches!(
+                error.error_type,
+                ValidationErrorType::UnprotectedThrowOnFieldError
+                    | ValidationErrorType::UnprotectedRequiredThrow
+            )
+        });
+        let hint = if !has_unprotected_directives {
             String::new()
         } else {
             "❌ @throwOnFieldError must not be used outside of @catch\n\
@@ -170,13 +271,30 @@ impl From<ValidationResult> for JsonValidationResult {
                 .map(JsonValidationError::from)
                 .collect(),
             hint,
+            warnings: result
+                .warnings
+                .into_iter()
+                .map(JsonValidationError::from)
+                .collect(),
+            diagnostics: Vec::new(),
+            fixed_baseline_entries: Vec::new(),
         }
     }
 }
 
 impl std::fmt::Display for ValidationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "\n🚨 {}", self.error_type)?;
+        let icon = match self.severity {
+            Severity::Error => "🚨",
+            Severity::Warning => "⚠️ ",
+            Severity::Info => "ℹ️ ",
+        };
+        writeln!(
+            f,
+            "\n{icon} {} [{}]",
+            self.error_type,
+            self.error_type.code()
+        )?;
         writeln!(f)?;
 
         // Git root for relative paths in snapshots
@@ -193,14 +311,19 @@ impl std::fmt::Display for ValidationError {
             .unwrap_or(&self.context.query_file);
         let query_path_str = query_relative_path.display().to_string();
 
-        if let (Some(line), Some(col)) = (self.context.line, self.context.col) {
-            writeln!(
+        let label = self.label();
+        match (self.context.line, self.context.col) {
+            (Some(line), Some(col)) => writeln!(
                 f,
-                "query: {} {}:{}:{}",
+                "{label}: {} {}:{}:{}",
                 self.context.query_name, query_path_str, line, col
-            )?;
-        } else {
-            writeln!(f, "query: {} {}", self.context.query_name, query_path_str)?;
+            )?,
+            (Some(line), None) => writeln!(
+                f,
+                "{label}: {} {}:{}",
+                self.context.query_name, query_path_str, line
+            )?,
+            _ => writeln!(f, "{label}: {} {}", self.context.query_name, query_path_str)?,
         }
         if let (Some(fragment_name), Some(fragment_file)) =
             (&self.context.fragment_name, &self.context.fragment_file)
@@ -218,14 +341,41 @@ impl std::fmt::Display for ValidationError {
         // Location info is now included in the query line above
         writeln!(f)?;
 
+        if !self.code_frame.is_empty() {
+            write!(f, "{}", self.code_frame)?;
+            writeln!(f)?;
+        }
+
+        if !self.explanation.is_empty() {
+            writeln!(f, "{}", self.explanation)?;
+            writeln!(f)?;
+        }
+
         // Show tree visualization
-        writeln!(f, "Query Structure:")?;
-        write!(f, "{}", self.tree_visualization)
+        if !self.tree_visualization.is_empty() {
+            writeln!(f, "Query Structure:")?;
+            write!(f, "{}", self.tree_visualization)?;
+        }
+        Ok(())
     }
 }
 
 impl std::fmt::Display for ValidationResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.warnings.is_empty() {
+            writeln!(
+                f,
+                "⚠️  Found {} warning{}:",
+                self.warnings.len(),
+                if self.warnings.len() == 1 { "" } else { "s" }
+            )?;
+            for warning in &self.warnings {
+                write!(f, "{warning}")?;
+            }
+            writeln!(f, "{}", "-".repeat(80))?;
+            writeln!(f)?;
+        }
+
         if self.errors.is_empty() {
             write!(
                 f,
@@ -257,115 +407,79 @@ impl std::fmt::Display for ValidationResult {
 
 impl std::error::Error for ValidationError {}
 
-// Protection state for efficient subtree skipping
-#[derive(Debug, Clone)]
-enum ProtectionState {
-    Protected,   // @catch found, skip all nested validation
-    Unprotected, // No @catch protection, must validate all directives
-}
-
-// Validation context with optimized protection tracking
-struct RegistryValidationContext<'a> {
-    registry: &'a GraphQLRegistry,
-    query_name: &'a str,
-    query_file: &'a std::path::Path,
-    protection_state: ProtectionState,
-    current_fragment_file: Option<PathBuf>,
-    current_fragment_name: Option<String>,
-    errors: Vec<ValidationError>,
-    visiting_fragments: FxHashSet<String>, // Cycle detection
+// Entry point for optimized registry-based validation with all built-in rules
+// Provides significant performance improvements over dependency graph approach
+pub fn validate_registry(registry: &GraphQLRegistry) -> ValidationResult {
+    validate_registry_with_rules(registry, &RuleSet::default())
 }
 
-// Helper function to find directive line and column information
-fn get_directive_position(
-    directives: &[crate::parsers::graphql_parser::Directive],
-    directive_type: &DirectiveType,
-) -> (Option<u32>, Option<u32>) {
-    directives
-        .iter()
-        .find(|d| d.directive_type == *directive_type)
-        .map(|d| (Some(d.line), Some(d.col)))
-        .unwrap_or((None, None))
-}
+// Runs the enabled rules: one parallel traversal of all operations, then the registry-wide checks
+pub fn validate_registry_with_rules(
+    registry: &GraphQLRegistry,
+    rules: &RuleSet,
+) -> ValidationResult {
+    let active_rules = rules.active_rules();
 
-// Entry point for optimized registry-based validation
-// Provides significant performance improvements over dependency graph approach
-pub fn validate_registry(registry: &GraphQLRegistry) -> ValidationResult {
     // Thread-safe error collection for parallel processing
     let errors_mutex = Mutex::new(Vec::new());
 
-    // Collect queries into vector for parallel processing
+    // Collect queries into vector for parallel processing (every definition of duplicated names)
     let queries: Vec<_> = registry
         .queries
         .iter()
-        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .flat_map(|entry| entry.value().clone())
         .collect();
 
     // Process queries in parallel for maximum performance
-    queries.par_iter().for_each(|(query_name, query)| {
+    queries.par_iter().for_each(|query| {
         // Initialize per-query validation context
-        let mut ctx = RegistryValidationContext {
+        let mut ctx = RuleContext {
             registry,
-            query_name,
-            query_file: &query.file_path,
+            definition: Definition::Operation(query),
             protection_state: ProtectionState::Unprotected,
+            location: "query level".to_string(),
             current_fragment_file: None,
             current_fragment_name: None,
+            spreads: Vec::new(),
             errors: Vec::new(),
+        };
+        let mut traversal = Traversal {
+            rules: &active_rules,
             visiting_fragments: FxHashSet::default(),
         };
+        traversal.operation(query, &mut ctx);
 
-        // Check for query-level @catch protection
-        let has_query_catch = query
-            .directives
-            .iter()
-            .any(|d| d.directive_type == DirectiveType::Catch);
-
-        if has_query_catch {
-            ctx.protection_state = ProtectionState::Protected;
-        }
-
-        // Validate query-level directives
-        for directive in &query.directives {
-            match directive.directive_type {
-                DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => {
-                    if let ProtectionState::Unprotected = ctx.protection_state {
-                        let (line, col) =
-                            get_directive_position(&query.directives, &directive.directive_type);
-                        let context = ErrorContext {
-                            query_name: ctx.query_name.to_string(),
-                            query_file: ctx.query_file.to_path_buf(),
-                            location_path: "query level".to_string(),
-                            fragment_file: None,
-                            fragment_name: None,
-                            line,
-                            col,
-                        };
-
-                        let tree_visualization = create_optimized_tree_visualization(
-                            ctx.registry,
-                            ctx.query_name,
-                            ctx.query_file,
-                            Some("query level"),
-                        );
-                        let explanation = String::new();
-
-                        ctx.errors.push(ValidationError {
-                            error_type: ValidationErrorType::UnprotectedThrowOnFieldError,
-                            context,
-                            tree_visualization,
-                            explanation,
-                        });
-                    }
-                }
-                _ => {} // Other directives (like @catch) don't need validation
-            }
+        // Collect errors in thread-safe manner
+        if !ctx.errors.is_empty() {
+            let mut global_errors = errors_mutex.lock().unwrap();
+            global_errors.extend(ctx.errors);
         }
+    });
 
-        // Validate query selections with smart subtree skipping
-        validate_selections_optimized(&query.selections, "query", &mut ctx);
+    // Fragments are only walked for the spreads written in them, their fields and directives
+    // are validated in the context of the operations spreading them
+    let fragments: Vec<_> = registry
+        .fragments
+        .iter()
+        .flat_map(|entry| entry.value().clone())
+        .collect();
+    fragments.par_iter().for_each(|fragment| {
+        let mut ctx = RuleContext {
+            registry,
+            definition: Definition::Fragment(fragment),
+            protection_state: ProtectionState::Unprotected,
+            location: "fragment".to_string(),
+            current_fragment_file: None,
+            current_fragment_name: None,
+            spreads: Vec::new(),
+            errors: Vec::new(),
+        };
+        let traversal = Traversal {
+            rules: &active_rules,
+            visiting_fragments: FxHashSet::default(),
+        };
+        traversal.fragment_spreads(&fragment.selections, &mut ctx);
 
-        // Collect errors in thread-safe manner
         if !ctx.errors.is_empty() {
             let mut global_errors = errors_mutex.lock().unwrap();
             global_errors.extend(ctx.errors);
@@ -381,284 +495,240 @@ pub fn validate_registry(registry: &GraphQLRegistry) -> ValidationResult {
         a.context
             .query_name
             .cmp(&b.context.query_name)
+            .then_with(|| a.context.query_file.cmp(&b.context.query_file))
             .then_with(|| a.context.location_path.cmp(&b.context.location_path))
     });
 
-    ValidationResult { errors: all_errors }
+    for rule in &active_rules {
+        all_errors.extend(rule.check_registry(registry));
+    }
+
+    let mut result = ValidationResult::new();
+    for mut error in all_errors {
+        // Overrides apply to the file that owns the finding, not the operation spreading it
+        if let Some(severity) = rules.level(&error.error_type, error.context.owner_file()) {
+            error.severity = severity;
+            result.add_error(error);
+        }
+    }
+    result
 }
 
-// Core optimized validation logic with protection state tracking
-fn validate_selections_optimized(
-    selections: &[Selection],
-    current_location: &str,
-    ctx: &mut RegistryValidationContext,
-) {
-    for selection in selections {
-        match selection {
-            Selection::Field(field) => {
-                let field_location = format!("{}.{}", current_location, field.name);
+// Walks one operation with protection state tracking and hands every node to the rules
+struct Traversal<'r> {
+    rules: &'r [&'r dyn Rule],
+    visiting_fragments: FxHashSet<String>, // Cycle detection
+}
 
-                // Check field-level @catch for protection
-                let field_has_catch = field
-                    .directives
-                    .iter()
-                    .any(|d| d.directive_type == DirectiveType::Catch);
+impl Traversal<'_> {
+    fn operation(&mut self, query: &QueryOperation, ctx: &mut RuleContext) {
+        // Check for query-level @catch protection
+        if has_catch(&query.directives) {
+            ctx.protection_state = ProtectionState::Protected;
+        }
 
-                // Validate field directives based on PARENT's protection state
-                validate_field_directives_optimized(field, &field_location, ctx);
+        for rule in self.rules {
+            rule.enter_operation(query, ctx);
+        }
+        self.directives(&query.directives, DirectiveOwner::Operation, ctx);
 
-                // Create new protection state for this field's CHILDREN
-                let original_state = ctx.protection_state.clone();
-                if field_has_catch {
-                    ctx.protection_state = ProtectionState::Protected;
-                }
+        // Validate query selections with smart subtree skipping
+        ctx.location = "query".to_string();
+        self.selections(&query.selections, ctx);
 
-                // Recursively validate nested selections with updated protection state
-                validate_selections_optimized(&field.selections, &field_location, ctx);
+        for rule in self.rules {
+            rule.leave_operation(query, ctx);
+        }
+    }
 
-                // Restore original protection state
-                ctx.protection_state = original_state;
+    fn directives(&self, directives: &[Directive], owner: DirectiveOwner, ctx: &mut RuleContext) {
+        for directive in directives {
+            for rule in self.rules {
+                rule.directive(directive, owner, ctx);
             }
-            Selection::FragmentSpread(spread) => {
-                let spread_location = format!("{}...{}", current_location, spread.name);
+        }
+    }
 
-                // OPTIMIZATION: Skip fragment processing if we're in a protected subtree
-                match ctx.protection_state {
-                    ProtectionState::Protected => {
-                        // Skip entire fragment - we know it's protected
-                        continue;
+    // Core optimized validation logic with protection state tracking
+    fn selections(&mut self, selections: &[Selection], ctx: &mut RuleContext) {
+        let current_location = ctx.location.clone();
+
+        for selection in selections {
+            let original_state = ctx.protection_state;
+
+            match selection {
+                Selection::Field(field) => {
+                    ctx.location = format!("{}.{}", current_location, field.name);
+
+                    // Field directives are validated based on PARENT's protection state
+                    for rule in self.rules {
+                        rule.enter_field(field, ctx);
                     }
-                    ProtectionState::Unprotected => {
-                        // Must validate fragment content on-demand
-                        validate_fragment_spread_optimized(spread, &spread_location, ctx);
+                    self.directives(&field.directives, DirectiveOwner::Field(field), ctx);
+
+                    // Field-level @catch protects this field's CHILDREN
+                    if has_catch(&field.directives) {
+                        ctx.protection_state = ProtectionState::Protected;
                     }
-                }
-            }
-            Selection::InlineFragment(inline) => {
-                let fragment_name = inline
-                    .type_condition
-                    .as_ref()
-                    .and_then(|tc| tc.strip_suffix("Fragment"))
-                    .unwrap_or("InlineFragment");
-                let inline_location = format!("{current_location}...{fragment_name}");
+                    self.selections(&field.selections, ctx);
 
-                // Check inline fragment @catch protection
-                let fragment_has_catch = inline
-                    .directives
-                    .iter()
-                    .any(|d| d.directive_type == DirectiveType::Catch);
+                    for rule in self.rules {
+                        rule.leave_field(field, ctx);
+                    }
+                }
+                Selection::FragmentSpread(spread) => {
+                    ctx.location = format!("{}...{}", current_location, spread.name);
+                    // Spreads written in the operation itself, those of fragments are
+                    // visited when the fragment definitions are walked
+                    if ctx.spreads.is_empty() {
+                        for rule in self.rules {
+                            rule.spread(spread, ctx);
+                        }
+                    }
 
-                let original_state = ctx.protection_state.clone();
-                if fragment_has_catch {
-                    ctx.protection_state = ProtectionState::Protected;
+                    // OPTIMIZATION: Skip entire fragment if we're in a protected subtree
+                    if ctx.is_protected() {
+                        continue;
+                    }
+                    self.fragment_spread(spread, ctx);
                 }
+                Selection::InlineFragment(inline) => {
+                    let fragment_name = inline
+                        .type_condition
+                        .as_ref()
+                        .and_then(|tc| tc.strip_suffix("Fragment"))
+                        .unwrap_or("InlineFragment");
+                    ctx.location = format!("{current_location}...{fragment_name}");
 
-                // Validate inline fragment directives
-                validate_inline_fragment_directives_optimized(inline, &inline_location, ctx);
+                    // Inline fragment @catch also protects its own directives
+                    if has_catch(&inline.directives) {
+                        ctx.protection_state = ProtectionState::Protected;
+                    }
 
-                // Recursively validate fragment selections
-                validate_selections_optimized(&inline.selections, &inline_location, ctx);
+                    for rule in self.rules {
+                        rule.enter_inline_fragment(inline, ctx);
+                    }
+                    self.directives(
+                        &inline.directives,
+                        DirectiveOwner::InlineFragment(inline),
+                        ctx,
+                    );
+                    self.selections(&inline.selections, ctx);
 
-                // Restore original protection state
-                ctx.protection_state = original_state;
+                    for rule in self.rules {
+                        rule.leave_inline_fragment(inline, ctx);
+                    }
+                }
             }
+
+            // Restore original protection state
+            ctx.protection_state = original_state;
         }
+
+        ctx.location = current_location;
     }
-}
 
-// Optimized field directive validation with protection awareness
-fn validate_field_directives_optimized(
-    field: &crate::parsers::graphql_parser::FieldSelection,
-    field_location: &str,
-    ctx: &mut RegistryValidationContext,
-) {
-    for directive in &field.directives {
-        match directive.directive_type {
-            DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => {
-                // OPTIMIZATION: Only validate if not protected
-                if let ProtectionState::Unprotected = ctx.protection_state {
-                    let (line, col) =
-                        get_directive_position(&field.directives, &directive.directive_type);
-                    let context = ErrorContext {
-                        query_name: ctx.query_name.to_string(),
-                        query_file: ctx.query_file.to_path_buf(),
-                        location_path: field_location.to_string(),
-                        fragment_file: ctx.current_fragment_file.clone(),
-                        fragment_name: ctx.current_fragment_name.clone(),
-                        line,
-                        col,
-                    };
-
-                    let tree_visualization = create_optimized_tree_visualization(
-                        ctx.registry,
-                        ctx.query_name,
-                        ctx.query_file,
-                        Some(field_location),
-                    );
-                    let explanation = String::new();
-
-                    ctx.errors.push(ValidationError {
-                        error_type: ValidationErrorType::UnprotectedThrowOnFieldError,
-                        context,
-                        tree_visualization,
-                        explanation,
-                    });
+    // Walks the selections of a fragment definition for its spreads without following them
+    fn fragment_spreads(&self, selections: &[Selection], ctx: &mut RuleContext) {
+        let current_location = ctx.location.clone();
+
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    ctx.location = format!("{}.{}", current_location, field.name);
+                    self.fragment_spreads(&field.selections, ctx);
+                }
+                Selection::FragmentSpread(spread) => {
+                    ctx.location = format!("{}...{}", current_location, spread.name);
+                    for rule in self.rules {
+                        rule.spread(spread, ctx);
+                    }
+                }
+                Selection::InlineFragment(inline) => {
+                    let fragment_name = inline
+                        .type_condition
+                        .as_ref()
+                        .and_then(|tc| tc.strip_suffix("Fragment"))
+                        .unwrap_or("InlineFragment");
+                    ctx.location = format!("{current_location}...{fragment_name}");
+                    self.fragment_spreads(&inline.selections, ctx);
                 }
-            }
-            DirectiveType::Catch => {
-                // @catch directive validation happens in selection processing
             }
         }
+
+        ctx.location = current_location;
     }
-}
 
-// On-demand fragment resolution and validation with cycle detection
-fn validate_fragment_spread_optimized(
-    spread: &crate::parsers::graphql_parser::FragmentSpread,
-    spread_location: &str,
-    ctx: &mut RegistryValidationContext,
-) {
-    // Check spread-level directives first
-    for directive in &spread.directives {
-        match directive.directive_type {
-            DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => {
-                if let ProtectionState::Unprotected = ctx.protection_state {
-                    let (line, col) =
-                        get_directive_position(&spread.directives, &directive.directive_type);
-                    let context = ErrorContext {
-                        query_name: ctx.query_name.to_string(),
-                        query_file: ctx.query_file.to_path_buf(),
-                        location_path: spread_location.to_string(),
-                        fragment_file: ctx.current_fragment_file.clone(),
-                        fragment_name: Some(spread.name.clone()),
-                        line,
-                        col,
-                    };
-
-                    let tree_visualization = create_optimized_tree_visualization(
-                        ctx.registry,
-                        ctx.query_name,
-                        ctx.query_file,
-                        Some(spread_location),
-                    );
-                    let explanation = String::new();
-
-                    ctx.errors.push(ValidationError {
-                        error_type: ValidationErrorType::UnprotectedThrowOnFieldError,
-                        context,
-                        tree_visualization,
-                        explanation,
-                    });
-                }
-            }
-            DirectiveType::Catch => {
-                // Spread has @catch, so entire fragment becomes protected
+    // On-demand fragment resolution and validation with cycle detection
+    fn fragment_spread(&mut self, spread: &FragmentSpread, ctx: &mut RuleContext) {
+        // Spread-level directives come first, a @catch protects everything after it
+        for directive in &spread.directives {
+            if directive.directive_type == DirectiveType::Catch {
                 ctx.protection_state = ProtectionState::Protected;
-                return; // Skip processing fragment content
+            }
+            for rule in self.rules {
+                rule.directive(directive, DirectiveOwner::FragmentSpread(spread), ctx);
             }
         }
-    }
 
-    // Prevent infinite recursion from circular fragments
-    if ctx.visiting_fragments.contains(&spread.name) {
-        // Circular dependency detected - skip to prevent stack overflow
-        return;
-    }
-
-    // Resolve fragment on-demand only if needed
-    if let Some(fragment_entry) = ctx.registry.fragments.get(&spread.name) {
-        let fragment = fragment_entry.value();
-
-        // Check if fragment itself has @catch protection
-        let fragment_has_catch = fragment
-            .directives
-            .iter()
-            .any(|d| d.directive_type == DirectiveType::Catch);
-
-        let original_state = ctx.protection_state.clone();
-        let original_fragment_file = ctx.current_fragment_file.clone();
-        let original_fragment_name = ctx.current_fragment_name.clone();
+        // Skip processing fragment content of protected spreads and circular fragments
+        if ctx.is_protected() || self.visiting_fragments.contains(&spread.name) {
+            return;
+        }
 
-        if fragment_has_catch {
-            ctx.protection_state = ProtectionState::Protected;
+        // Resolve fragment on-demand only if needed
+        let fragment = ctx.registry.fragment(&spread.name);
+        for rule in self.rules {
+            rule.enter_fragment_spread(spread, fragment.as_ref(), ctx);
         }
 
-        // Update fragment context
-        ctx.current_fragment_file = Some(fragment.file_path.clone());
-        ctx.current_fragment_name = Some(fragment.name.clone());
+        if let Some(fragment) = &fragment {
+            let original_fragment_file = ctx.current_fragment_file.clone();
+            let original_fragment_name = ctx.current_fragment_name.clone();
+            ctx.spreads.push(SpreadLocation {
+                fragment_name: spread.name.clone(),
+                file_path: original_fragment_file
+                    .clone()
+                    .unwrap_or_else(|| ctx.definition.file_path().clone()),
+                line: spread.line,
+                col: spread.col,
+            });
+
+            // Check if fragment itself has @catch protection
+            if has_catch(&fragment.directives) {
+                ctx.protection_state = ProtectionState::Protected;
+            }
 
-        // Add to visiting set for cycle detection
-        ctx.visiting_fragments.insert(spread.name.clone());
+            // Update fragment context
+            ctx.current_fragment_file = Some(fragment.file_path.clone());
+            ctx.current_fragment_name = Some(fragment.name.clone());
 
-        // Validate fragment selections
-        validate_selections_optimized(&fragment.selections, spread_location, ctx);
+            self.visiting_fragments.insert(spread.name.clone());
+            self.selections(&fragment.selections, ctx);
+            self.visiting_fragments.remove(&spread.name);
 
-        // Remove from visiting set after processing
-        ctx.visiting_fragments.remove(&spread.name);
+            // Restore original context
+            ctx.spreads.pop();
+            ctx.current_fragment_file = original_fragment_file;
+            ctx.current_fragment_name = original_fragment_name;
+        }
 
-        // Restore original context
-        ctx.protection_state = original_state;
-        ctx.current_fragment_file = original_fragment_file;
-        ctx.current_fragment_name = original_fragment_name;
+        for rule in self.rules {
+            rule.leave_fragment_spread(spread, ctx);
+        }
     }
 }
 
-// Validate inline fragment directives with protection awareness
-fn validate_inline_fragment_directives_optimized(
-    inline: &crate::parsers::graphql_parser::InlineFragment,
-    inline_location: &str,
-    ctx: &mut RegistryValidationContext,
-) {
-    for directive in &inline.directives {
-        match directive.directive_type {
-            DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => {
-                if let ProtectionState::Unprotected = ctx.protection_state {
-                    let fragment_name = inline
-                        .type_condition
-                        .as_ref()
-                        .and_then(|tc| tc.strip_suffix("Fragment"))
-                        .map(|name| name.to_string());
-
-                    let (line, col) =
-                        get_directive_position(&inline.directives, &directive.directive_type);
-                    let context = ErrorContext {
-                        query_name: ctx.query_name.to_string(),
-                        query_file: ctx.query_file.to_path_buf(),
-                        location_path: inline_location.to_string(),
-                        fragment_file: ctx.current_fragment_file.clone(),
-                        fragment_name,
-                        line,
-                        col,
-                    };
-
-                    let tree_visualization = create_optimized_tree_visualization(
-                        ctx.registry,
-                        ctx.query_name,
-                        ctx.query_file,
-                        Some(inline_location),
-                    );
-                    let explanation = String::new();
-
-                    ctx.errors.push(ValidationError {
-                        error_type: ValidationErrorType::UnprotectedThrowOnFieldError,
-                        context,
-                        tree_visualization,
-                        explanation,
-                    });
-                }
-            }
-            DirectiveType::Catch => {
-                // Will be handled in selection processing
-            }
-        }
-    }
+fn has_catch(directives: &[Directive]) -> bool {
+    directives
+        .iter()
+        .any(|d| d.directive_type == DirectiveType::Catch)
 }
 
 // Create optimized tree visualization without full dependency graph expansion
-fn create_optimized_tree_visualization(
+pub(crate) fn create_optimized_tree_visualization(
     registry: &GraphQLRegistry,
-    query_name: &str,
-    query_file: &std::path::Path,
+    query: &QueryOperation,
     error_location: Option<&str>,
 ) -> String {
     let mut formatter = TreeFormatter::new();
@@ -669,55 +739,58 @@ fn create_optimized_tree_visualization(
         .unwrap()
         .to_path_buf();
 
-    let relative_path = query_file.strip_prefix(&git_root).unwrap_or(query_file);
+    let relative_path = query
+        .file_path
+        .strip_prefix(&git_root)
+        .unwrap_or(&query.file_path);
 
     formatter.add_line(
         0,
-        &format!("📄 Query: {} ({})", query_name, relative_path.display()),
+        &format!(
+            "📄 {:?}: {} ({})",
+            query.kind,
+            query.name,
+            relative_path.display()
+        ),
     );
 
-    // Get query from registry
-    if let Some(query_entry) = registry.queries.get(query_name) {
-        let query = query_entry.value();
-
-        // Add query-level directives
-        if !query.directives.is_empty() {
-            formatter.add_line(1, "🏷️  Query Directives:");
-            for directive in &query.directives {
-                let emoji = match directive.directive_type {
-                    DirectiveType::Catch => "🧤",
-                    DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => "☄️",
-                };
-                let highlight = if let Some(error_loc) = error_location {
-                    if error_loc == "query level" {
-                        " ❌"
-                    } else {
-                        ""
-                    }
+    // Add query-level directives
+    if !query.directives.is_empty() {
+        formatter.add_line(1, "🏷️  Query Directives:");
+        for directive in &query.directives {
+            let emoji = match directive.directive_type {
+                DirectiveType::Catch => "🧤",
+                DirectiveType::ThrowOnFieldError | DirectiveType::RequiredThrow => "☄️",
+            };
+            let highlight = if let Some(error_loc) = error_location {
+                if error_loc == "query level" {
+                    " ❌"
                 } else {
                     ""
-                };
-                formatter.add_line(
-                    2,
-                    &format!("{} @{}{}", emoji, directive.directive_type, highlight),
-                );
-            }
-        }
-
-        // Add query selections
-        if !query.selections.is_empty() {
-            formatter.add_line(1, "🔍 Selections:");
-            format_selections_for_optimized_visualization_with_path(
-                &mut formatter,
-                &query.selections,
+                }
+            } else {
+                ""
+            };
+            formatter.add_line(
                 2,
-                error_location,
-                registry,
-                "query", // Track the current path
+                &format!("{} @{}{}", emoji, directive.directive_type, highlight),
             );
         }
     }
 
+    // Add query selections
+    if !query.selections.is_empty() {
+        formatter.add_line(1, "🔍 Selections:");
+        format_selections_for_optimized_visualization_with_path(
+            &mut formatter,
+            &query.selections,
+            2,
+            error_location,
+            registry,
+            "query", // Track the current path
+        );
+    }
+
     formatter.to_string()
 }
 
@@ -810,8 +883,7 @@ fn format_selections_for_optimized_visualization_with_path(
                 formatter.add_line(depth, &spread_text);
 
                 // Optionally show fragment content for better debugging
-                if let Some(fragment_entry) = registry.fragments.get(&spread.name) {
-                    let fragment = fragment_entry.value();
+                if let Some(fragment) = registry.fragment(&spread.name) {
                     if !fragment.selections.is_empty() {
                         formatter.add_line(depth + 1, "Fragment Content:");
                         format_selections_for_optimized_visualization_with_path(
@@ -900,9 +972,7 @@ mod tests {
                 .filter_map(|entry| entry.ok())
                 .filter(|entry| {
                     let path = entry.path();
-                    path.is_file()
-                        && (path.extension() == Some(std::ffi::OsStr::new("ts"))
-                            || path.extension() == Some(std::ffi::OsStr::new("tsx")))
+                    path.is_file() && crate::parsers::is_supported_file(&path)
                 })
                 .collect();
 
@@ -962,6 +1032,27 @@ mod tests {
         insta::assert_snapshot!(result_message);
     }
 
+    #[test]
+    fn test_disabled_rules_are_skipped() {
+        let files = collect_fixture_files("invalid");
+        let registry = process_files(&files);
+
+        let mut rules = RuleSet::default();
+        rules.disable(ValidationErrorType::UnprotectedThrowOnFieldError);
+        let result = validate_registry_with_rules(&registry, &rules);
+        let codes: FxHashSet<&str> = result
+            .errors
+            .iter()
+            .map(|error| error.error_type.code())
+            .collect();
+        assert!(!codes.contains("GQS001"));
+        assert!(codes.contains("GQS002"));
+
+        // Without any enabled finding the protection rule doesn't run at all
+        rules.disable(ValidationErrorType::UnprotectedRequiredThrow);
+        assert_eq!(rules.active_rules().len(), 4);
+    }
+
     #[test]
     fn test_json_validation_result_valid_fixtures() {
         let files = collect_fixture_files("valid");